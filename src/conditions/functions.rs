@@ -1,6 +1,10 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use super::ConditionValue;
+use super::{Condition, ConditionValue, ReferenceValue};
+use crate::facts::FactValue;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Fn {
@@ -8,10 +12,30 @@ pub enum Fn {
     And(Vec<ConditionValue>),
     Or(Vec<ConditionValue>),
     Not(Box<ConditionValue>),
+    /// Coalesce(a, b, ...). The first value that is present.
+    Coalesce(Vec<ConditionValue>),
     //False()
-    //If(test, true_value, false_value)
-    //Switch(exp, val1, val2, ...)
-    //Xor(a, b, ...)
+    /// If(test, true_value, false_value). A missing test is false, as in `And` and `Or`, and
+    /// the result is FALSE when the test is false and there is no false_value.
+    If(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// IfNull(value, fallback). The value, or the fallback when the value is missing.
+    IfNull(Box<ConditionValue>, Box<ConditionValue>),
+    /// Switch(exp, [(val1, result1), (val2, result2), ...], default). The result for the first
+    /// value equal to exp.
+    Switch(
+        Box<ConditionValue>,
+        Vec<(ConditionValue, ConditionValue)>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// ToBoolean(value). Booleans as they are, numbers are true when not zero, and the text
+    /// TRUE, FALSE, YES, NO, 1 and 0 in any case.
+    ToBoolean(Box<ConditionValue>),
+    /// Xor(a, b, ...). True when an odd number of the values are true.
+    Xor(Vec<ConditionValue>),
 
     // - Comparison -
     GreaterThan(Vec<ConditionValue>),
@@ -19,91 +43,260 @@ pub enum Fn {
     Equal(Vec<ConditionValue>),
 
     // - Date and time -
-    //Date(y, m, d)
-    //DateDif(a, b, unit)
-    //DateValue(text)
-    //Day(date)
-    //Days(end, start)
-    //days360
-    //EDate(date, months)
-    //EOMonth(date, months)
-    //Hour(serial_time)
-    //ISOWeekNum(date)
-    //Minute(serial_number)
-    //Month(serial_number)
-    //NetWorkDays(start, end, holidays)
-    //NetWorkDaysIntl(start, end, weekend, holidays)
-    //Now()
-    //Second(serial_number)
+    /// Date(year, month, day). Out of range months and days roll over, as in Excel.
+    Date(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    /// DateDif(start, end, unit) where unit is one of "Y", "M", "D", "MD", "YM" or "YD".
+    DateDif(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    DateValue(Box<ConditionValue>),
+    Day(Box<ConditionValue>),
+    /// Days(end, start)
+    Days(Box<ConditionValue>, Box<ConditionValue>),
+    /// Days360(start, end, european_method)
+    Days360(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// EDate(date, months)
+    EDate(Box<ConditionValue>, Box<ConditionValue>),
+    /// EOMonth(date, months)
+    EOMonth(Box<ConditionValue>, Box<ConditionValue>),
+    Hour(Box<ConditionValue>),
+    ISOWeekNum(Box<ConditionValue>),
+    Minute(Box<ConditionValue>),
+    Month(Box<ConditionValue>),
+    /// NetWorkDays(start, end, holidays). Counts Monday to Friday, inclusive of both ends.
+    NetWorkDays(Box<ConditionValue>, Box<ConditionValue>, Option<Holidays>),
+    /// NetWorkDaysIntl(start, end, weekend, holidays)
+    NetWorkDaysIntl(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Holidays>,
+    ),
+    /// The current instant, read from the environment's clock.
+    Now,
+    Second(Box<ConditionValue>),
     //Time(hour, minute, second)
     //TimeValue(text)
-    //Today()
-    //WeekDay(serial_number, return_type)
-    //WeekNum(serial_number, return_type)
-    //WorkDay(start, days, holidays)
-    //WorkDayIntl(start, days, weekend, holidays)
-    //Year(date)
-    //YearFrac(start, end, basis)
+    /// The current date, read from the environment's clock.
+    Today,
+    /// WeekDay(date, return_type)
+    WeekDay(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    /// WeekNum(date, return_type)
+    WeekNum(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    /// WorkDay(start, days, holidays). The date `days` business days before or after start.
+    WorkDay(Box<ConditionValue>, Box<ConditionValue>, Option<Holidays>),
+    /// WorkDayIntl(start, days, weekend, holidays)
+    WorkDayIntl(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Holidays>,
+    ),
+    Year(Box<ConditionValue>),
+    /// YearFrac(start, end, basis)
+    YearFrac(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
 
     // - Lookup and Reference
-    // NA?
+    // The lookup functions search the first column of a table for a key and return the value
+    // in another column of the same row, counting columns from 1. Keys match as Equals compares
+    // them, and the closest key when one is not found is the closest key of the same type.
+    /// Rows(array). The number of items in an array fact.
+    Rows(ReferenceValue),
+    /// VLookup(value, table, column, approximate). With approximate, the default as in Excel,
+    /// the row of the largest key not greater than the value, otherwise the row of the key.
+    VLookup(
+        Box<ConditionValue>,
+        Table,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// XLookup(value, table, column, if_not_found, match_mode). Match mode 0, the default,
+    /// finds the key, -1 the key or the next smaller key, and 1 the key or the next larger key.
+    XLookup(
+        Box<ConditionValue>,
+        Table,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+    ),
 
     // - Text -
+    // `Value` and `NumberValue` read numbers from text such as "1,234.50" or "12.5%", ignoring
+    // whitespace. Whole numbers are integers.
     //Char(number)
     //Clean(text)
     //Code(text)
-    //Concat(a, b, ...)
+    Concat(Vec<ConditionValue>),
     //Concatenate(a, b, c, ...)
     //Dollar(number, decimals)
-    //Exact(a, b)
-    //Find(find, within, start_num)
+    /// Exact(a, b). Case-sensitive text comparison.
+    Exact(Box<ConditionValue>, Box<ConditionValue>),
+    /// Extract(text, pattern, group). The text of a capture group, by number or name, in the
+    /// first match. The group defaults to the whole match.
+    Extract(Box<ConditionValue>, Pattern, Option<Box<ConditionValue>>),
+    /// Find(find, within, start_num). Case-sensitive, 1-based position of `find`.
+    Find(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
     //Fixed(number, decimals, no_commas)
-    //Left(text, num_chars)
-    //Len(text)
-    //Lower(text)
-    //Mid(text, start, end)
-    //NumberValue(text, decimal_separate, group_separator)
-    //Proper(text)
-    //Replace(old, start, num_chars, new_text)
-    //Rept(text, num)
-    //Right(text, num_chars)
-    //Search(find, within, start)
-    //Substitute(text, old, new, instance)
-    //Text(value, format_text)
-    //TextJoin(delimiter, ingore_empty, text1, text2, ...)
-    //Trim(text)
+    /// Left(text, num_chars)
+    Left(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    Len(Box<ConditionValue>),
+    Lower(Box<ConditionValue>),
+    /// Matches(text, pattern). True when the pattern matches anywhere in the text; anchor it with
+    /// `^` and `$` to match the whole text.
+    Matches(Box<ConditionValue>, Pattern),
+    /// Mid(text, start, num_chars)
+    Mid(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    /// NumberValue(text, decimal_separator, group_separator). Separators default to `.` and `,`.
+    NumberValue(
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+    ),
+    Proper(Box<ConditionValue>),
+    /// Replace(old, start, num_chars, new_text)
+    Replace(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    /// Rept(text, num)
+    Rept(Box<ConditionValue>, Box<ConditionValue>),
+    /// Right(text, num_chars)
+    Right(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    /// Search(find, within, start_num). Case-insensitive, 1-based position of `find`.
+    Search(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// Split(text, delimiter). The items of delimited text, such as "sale, new", as an array.
+    /// Items are trimmed of surrounding whitespace, and empty text has no items.
+    Split(Box<ConditionValue>, Box<ConditionValue>),
+    /// Substitute(text, old, new, instance). Replaces every occurrence when instance is omitted.
+    Substitute(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// Text(value, format_text). Formats numbers with codes such as `#,##0.00` or `0%`, and
+    /// dates with codes such as `yyyy-mm-dd hh:mm`. Other values are written as text.
+    Text(Box<ConditionValue>, Box<ConditionValue>),
+    /// TextJoin(delimiter, ignore_empty, a, b, ...). The values, expanding arrays, joined by the
+    /// delimiter. Missing values are skipped, and so is empty text when ignore_empty is true.
+    TextJoin(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Vec<ConditionValue>,
+    ),
+    /// Trim(text). Removes leading and trailing spaces and collapses runs of inner spaces.
+    Trim(Box<ConditionValue>),
     //Unichar(number)
     //Unicode(text)
-    //Upper(text)
-    //Value(text)
+    Upper(Box<ConditionValue>),
+    /// Value(text). Dates are their serial number.
+    Value(Box<ConditionValue>),
 
     // - Dynamic array -
-    // NA?
+    // Map and Filter evaluate their value, and Filter its condition, once for each item of an
+    // array, as an array condition does; within them `orders[].total` is the total of the
+    // current order. They produce arrays, which the functions over lists of values expand into
+    // their items. The set functions take arrays or lists of values, compare values as Equals
+    // does and give each value once.
+    /// All(array, condition). True when the condition holds for every item.
+    All(ReferenceValue, Box<Condition>),
+    /// Any(array, condition). True when the condition holds for at least one item.
+    Any(ReferenceValue, Box<Condition>),
+    /// Contains(values, value). True when the value is one of the values.
+    Contains(Vec<ConditionValue>, Box<ConditionValue>),
+    /// Difference(a, b). The values of a that are not in b.
+    Difference(Vec<ConditionValue>, Vec<ConditionValue>),
+    /// Filter(array, condition, value). The value of each item for which the condition holds.
+    /// The value defaults to the item itself, `array[]`, and items without it are skipped.
+    Filter(ReferenceValue, Box<Condition>, Option<Box<ConditionValue>>),
+    /// Intersection(a, b). The values of a that are also in b.
+    Intersection(Vec<ConditionValue>, Vec<ConditionValue>),
+    /// Map(array, value). The value of each item. Items without the value are skipped.
+    Map(ReferenceValue, Box<ConditionValue>),
+    /// Reduce(reduction, a, b, ...). Combine the values, expanding arrays, with `Sum`, `Min` or
+    /// `Max`.
+    Reduce(Reduction, Vec<ConditionValue>),
+    /// Sort(values, order). The values, expanding arrays, in ascending order, or descending when
+    /// order is -1.
+    Sort(Vec<ConditionValue>, Option<Box<ConditionValue>>),
+    /// Union(a, b, ...). The values of all of the lists.
+    Union(Vec<ConditionValue>),
+    /// Unique(a, b, ...). The values, expanding arrays, without repeats, in the order they first
+    /// appear.
+    Unique(Vec<ConditionValue>),
 
     // - Engineering -
-    //Bin2Dec(num)
-    //Bin2Hex(num)
-    //BitAnd(a, b)
-    //BitLShift(num, shift_amt)
-    //BitOr(a, b)
-    //BitRShift(num, shift_amt)
-    //BitXOr(a, b,)
+    // The bitwise functions take non-negative integers. Binary, octal and hexadecimal numbers
+    // are text of up to 10 digits, with negative numbers in two's complement, as in Excel.
+    Bin2Dec(Box<ConditionValue>),
+    /// Bin2Hex(number, places)
+    Bin2Hex(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    /// Bin2Oct(number, places)
+    Bin2Oct(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    BitAnd(Box<ConditionValue>, Box<ConditionValue>),
+    /// BitLShift(number, shift_amount). A negative shift_amount shifts right.
+    BitLShift(Box<ConditionValue>, Box<ConditionValue>),
+    BitOr(Box<ConditionValue>, Box<ConditionValue>),
+    /// BitRShift(number, shift_amount). A negative shift_amount shifts left.
+    BitRShift(Box<ConditionValue>, Box<ConditionValue>),
+    BitXOr(Box<ConditionValue>, Box<ConditionValue>),
     //Complex(real_num, i_num, suffix)
     //Convert(number, from, to)
-    //Dec2Bin(number, places)
-    //Dec2Oct(number, places)
+    /// Dec2Bin(number, places)
+    Dec2Bin(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    /// Dec2Hex(number, places)
+    Dec2Hex(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    /// Dec2Oct(number, places)
+    Dec2Oct(Box<ConditionValue>, Option<Box<ConditionValue>>),
     //Delta(a, b)
-    //Hex2Bin(number, places)
-    //Hex2Dec(number)
-    //Hex2Oct(number)
+    /// Hex2Bin(number, places)
+    Hex2Bin(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    Hex2Dec(Box<ConditionValue>),
+    /// Hex2Oct(number, places)
+    Hex2Oct(Box<ConditionValue>, Option<Box<ConditionValue>>),
     //Imabs(i_number)
     //Imaginary(i_number)
     //ImPower(i_number, number)
     //ImProduct(i_a, i_b, ...)
     //ImReal(i_number)
     //ImSub(i_number, i_number)
+    /// Oct2Bin(number, places)
+    Oct2Bin(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    Oct2Dec(Box<ConditionValue>),
+    /// Oct2Hex(number, places)
+    Oct2Hex(Box<ConditionValue>, Option<Box<ConditionValue>>),
 
     // - Financial -
+    // Rates are per period, so 6% a year paid monthly is 0.005. Irr, Rate and Xirr are solved
+    // iteratively and have no value when they don't converge.
     //AccrInt
     //AccrIntm
     //AmorDeGrc
@@ -123,29 +316,68 @@ pub enum Fn {
     //DOLLARFR
     //DURATION
     //EFFECT
-    //FV
+    /// Fv(rate, nper, pmt, pv, type). Future value of an investment with constant payments.
+    /// Type 1 means payments are due at the start of each period.
+    Fv(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+    ),
     //FVSCHEDULE
     //INTRATE
     //IPMT
-    //IRR
+    /// Irr(values, guess). Internal rate of return of periodic cash flows.
+    Irr(Vec<ConditionValue>, Option<Box<ConditionValue>>),
     //ISPMT
     //MDURATION
     //MIRR
     //NOMINAL
-    //NPER
-    //NPV
+    /// Nper(rate, pmt, pv, fv, type). The number of periods for an investment.
+    Nper(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// Npv(rate, value1, value2, ...). Net present value of cash flows at the end of each period.
+    Npv(Box<ConditionValue>, Vec<ConditionValue>),
     //ODDFPRICE
     //ODDFYIELD
     //ODDLPRICE
     //ODDLYIELD
     //PDURATION
-    //PMT
+    /// Pmt(rate, nper, pv, fv, type). The payment per period for a loan.
+    Pmt(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+    ),
     //PPMT
     //PRICE
     //PRICEDISC
     //PRICEMAT
-    //PV
-    //RATE
+    /// Pv(rate, nper, pmt, fv, type). Present value of an investment.
+    Pv(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// Rate(nper, pmt, pv, fv, type, guess). The interest rate per period of an annuity.
+    Rate(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+        Option<Box<ConditionValue>>,
+    ),
     //RECEIVED
     //RRI
     //SLN
@@ -154,134 +386,222 @@ pub enum Fn {
     //TBILLPRICE
     //TBILLYIELD
     //VDB
-    //XIRR
-    //XNPV
+    /// Xirr(values, dates, guess). Internal rate of return of cash flows on the given dates.
+    Xirr(
+        Vec<ConditionValue>,
+        Vec<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
+    /// Xnpv(rate, values, dates). Net present value of cash flows on the given dates.
+    Xnpv(
+        Box<ConditionValue>,
+        Vec<ConditionValue>,
+        Vec<ConditionValue>,
+    ),
     //YIELD
     //YIELDDISC
     //YIELDMAT
 
     // - Information -
     // Incomplete: NA?
+    /// IsBlank(val). True when the value is missing, such as an absent or null fact.
+    IsBlank(Box<ConditionValue>),
     //IsEven(val)
-    //IsLogical(val)
+    IsLogical(Box<ConditionValue>),
     //IsNonText(val)
-    //IsNumber(val)
+    IsNumber(Box<ConditionValue>),
     //IsOdd(val)
-    //IsText(val)
-    //N(val)
-    //Type(val)
+    IsText(Box<ConditionValue>),
+    /// N(val). Numbers as they are, booleans as 1 or 0, dates as Excel serial numbers and
+    /// anything else, including a missing value, as 0.
+    N(Box<ConditionValue>),
+    /// Type(val). 1 for numbers and dates, 2 for text, 4 for booleans and 64 for arrays, as in
+    /// Excel. There is no type for a missing value.
+    Type(Box<ConditionValue>),
     //T(value)
 
     // - Math -
-    //Abs(num)
+    Abs(Box<ConditionValue>),
     //Aggregate(function_num, options, ref1, ref2)
     //Arabic(roman_text)
     //Base(number, radix, min_length)
-    //Ceiling(num, significance)
+    /// Ceiling(num, significance). Rounds up to a multiple of significance, as in Excel.
+    Ceiling(Box<ConditionValue>, Box<ConditionValue>),
     //Ceiling.Math(number, significance, mode)
     //Ceiling.Precise(number, significance)
     //Combin(number, number_chosen)
     //CombinA(number, number_chose)
     //Decimal(number, radix)
+    /// Divide(numerator, denominator)
+    Divide(Box<ConditionValue>, Box<ConditionValue>),
     //Even(number)
-    //Exp(number)
+    Exp(Box<ConditionValue>),
     //Fact(number)
     //FactDouble(number)
-    //Floor(number, significance)
+    /// Floor(number, significance). Rounds down to a multiple of significance, as in Excel.
+    Floor(Box<ConditionValue>, Box<ConditionValue>),
     //Floor.Math(number, significance, mode)
     //Floor.Precise(number, significance)
     //Gcd(a, b, ...)
-    //Int(number)
+    /// Int(number). Rounds down to the nearest integer.
+    Int(Box<ConditionValue>),
     //LCM(a, b, c, ...)
-    //Ln(number)
-    //Log(number)
-    //Log10(number, base)
+    Ln(Box<ConditionValue>),
+    /// Log(number, base). The base defaults to 10.
+    Log(Box<ConditionValue>, Option<Box<ConditionValue>>),
+    Log10(Box<ConditionValue>),
     //MDeterm(array)
     //MinVerse(array)
     //MMult(array_a, array_b)
-    //Mod(number, divisor)
+    /// Mod(number, divisor). The result has the same sign as the divisor.
+    Mod(Box<ConditionValue>, Box<ConditionValue>),
     //MRound(number, significance)
     //MUnit(dimension)
     //Odd(number)
-    //Pi
-    //Power(number, power)
-    //Product(a, b, ...)
-    //Quotient(numerator, denominator)
-    //Rand()
-    //RandBetween(bottom, top)
+    Pi,
+    /// Power(number, power)
+    Power(Box<ConditionValue>, Box<ConditionValue>),
+    Product(Vec<ConditionValue>),
+    /// Quotient(numerator, denominator). The integer part of a division.
+    Quotient(Box<ConditionValue>, Box<ConditionValue>),
+    /// Rand(). A random number from 0 up to but not including 1.
+    Rand,
+    /// RandBetween(bottom, top). A random integer from bottom to top inclusive.
+    RandBetween(Box<ConditionValue>, Box<ConditionValue>),
     //Roman(number, from)
-    //Round(number, num_digits)
-    //RoundDown(number, num_digits)
-    //RoundUp(number, num_digits)
-    //Sign(number)
-    //Sqrt(number)
+    /// Round(number, num_digits). Halves round away from zero.
+    Round(Box<ConditionValue>, Box<ConditionValue>),
+    /// RoundDown(number, num_digits). Rounds towards zero.
+    RoundDown(Box<ConditionValue>, Box<ConditionValue>),
+    /// RoundUp(number, num_digits). Rounds away from zero.
+    RoundUp(Box<ConditionValue>, Box<ConditionValue>),
+    Sign(Box<ConditionValue>),
+    Sqrt(Box<ConditionValue>),
+    /// Subtract(a, b)
+    Subtract(Box<ConditionValue>, Box<ConditionValue>),
     //Subtotal(function_num, ref1, ref2, ...)
-    //Sum(a, b, ...)
-    //SumIf(range, criteria, sum_range)
+    Sum(Vec<ConditionValue>),
+    /// SumIf(array, criteria, sum_value). Sums `sum_value` over the array items matching criteria.
+    SumIf(ReferenceValue, Box<Condition>, Box<ConditionValue>),
     //SumIfs() ...
     //SumProduct(array1, array2, ...)
     //SumSq(a, b, ...)
     //SumX2My2(array_x, array_y)
     //SumX2Py2(array_x, array_y)
     //SumXMy2(array_x, array_y)
-    //Trunc(number, num_digits)
+    /// Trunc(number, num_digits)
+    Trunc(Box<ConditionValue>, Option<Box<ConditionValue>>),
 
     // - Trigonometry -
-    //ACos(number)
-    //ASin(number)
-    //ATan(number)
-    //ATan2(x, y)
-    //Cos(number)
-    //CosH(number)
-    //Cot(number)
-    //Csc(number)
-    //Degrees(angle)
-    //Radians(angle)
-    //Sec(number)
-    //Sin(number)
-    //SinH(number)
-    //Tan(number)
+    // Angles are in radians. Like the logarithms, a value outside a function's domain, such as
+    // ASin(2) or Cot(0), has no result.
+    ACos(Box<ConditionValue>),
+    ASin(Box<ConditionValue>),
+    ATan(Box<ConditionValue>),
+    /// ATan2(x, y). The angle of the point (x, y), in the same argument order as Excel.
+    ATan2(Box<ConditionValue>, Box<ConditionValue>),
+    Cos(Box<ConditionValue>),
+    CosH(Box<ConditionValue>),
+    Cot(Box<ConditionValue>),
+    Csc(Box<ConditionValue>),
+    /// Degrees(angle). Converts radians to degrees.
+    Degrees(Box<ConditionValue>),
+    /// Radians(angle). Converts degrees to radians.
+    Radians(Box<ConditionValue>),
+    Sec(Box<ConditionValue>),
+    Sin(Box<ConditionValue>),
+    SinH(Box<ConditionValue>),
+    Tan(Box<ConditionValue>),
+    TanH(Box<ConditionValue>),
 
     // - Statistical -
+    // Functions over lists of values expand array references such as `orders[].total` into
+    // every item of the array, unless they are evaluated within an array condition on it.
+    // The conditional aggregates evaluate their criteria once per array item, as an array
+    // condition does, and combine a value from each matching item. A group condition as the
+    // criteria covers the multi-criteria `Ifs` forms.
+    // The regression functions pair the nth known y with the nth known x, so both lists must
+    // resolve to the same number of values.
     //AveDev(a, b, ...)
-    //Average(a, b, ...)
+    Average(Vec<ConditionValue>),
     //AverageA(a, b, ...)
-    //AverageIf(range, criteria, average_range)
+    /// AverageIf(array, criteria, average_value)
+    AverageIf(ReferenceValue, Box<Condition>, Box<ConditionValue>),
     //AverageIfs
-    //BINOM.dist
-    //BinomDist
-    //Count(a, b, ...)
-    //CountA(a, b, ...)
+    /// BinomDist(successes, trials, probability, cumulative). The probability of exactly, or
+    /// when cumulative is true at most, that many successes. There is no value for more than
+    /// 1,000,000 trials.
+    BinomDist(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    /// Count(a, b, ...). The number of values that are numbers.
+    Count(Vec<ConditionValue>),
+    /// CountA(a, b, ...). The number of values that are present.
+    CountA(Vec<ConditionValue>),
     //CountBlank(a, b, ...)
-    //CountIf(range, criteria)
+    /// CountIf(array, criteria). The number of array items matching criteria.
+    CountIf(ReferenceValue, Box<Condition>),
     //CountIfs
     //DevSq(a, b, ...)
-    //Forecast(x, known_ys, known_xs)
+    /// Forecast(x, known_ys, known_xs). The y predicted at x by a least squares line through
+    /// the known points.
+    Forecast(
+        Box<ConditionValue>,
+        Vec<ConditionValue>,
+        Vec<ConditionValue>,
+    ),
     //Forecast.ets
     //Forecast.ets.Confint
     //Forecast.ets.seasonality
     //Forecast.ets.stat
-    //Forecast.linear
+    /// ForecastLinear(x, known_ys, known_xs). The same as Forecast.
+    ForecastLinear(
+        Box<ConditionValue>,
+        Vec<ConditionValue>,
+        Vec<ConditionValue>,
+    ),
     //Frequency(data_array, bins_array)
     //GeoMean(a, b, ...)
     //Harmean(a, b, ...)
-    //Intercept(known_ys, known_xs)
-    //Large(array, k)
+    /// Intercept(known_ys, known_xs)
+    Intercept(Vec<ConditionValue>, Vec<ConditionValue>),
+    /// Large(array, k). The k-th largest number, counting from 1.
+    Large(Vec<ConditionValue>, Box<ConditionValue>),
     //Linest(known_ys, known_xs, const, stats)
     //MaxA(a, b, ...)
-    //MaxIfs
-    //Median(a, b, ...)
+    /// MaxIfs(array, criteria, max_value)
+    MaxIfs(ReferenceValue, Box<Condition>, Box<ConditionValue>),
+    Median(Vec<ConditionValue>),
     Min(Vec<ConditionValue>),
     Max(Vec<ConditionValue>),
     //MinA(a, b, ...)
-    //MinIfs
-    //Mode(a, b, ...)
+    /// MinIfs(array, criteria, min_value)
+    MinIfs(ReferenceValue, Box<Condition>, Box<ConditionValue>),
+    /// Mode(a, b, ...). The most frequent number, or the first of them when tied.
+    Mode(Vec<ConditionValue>),
     //Mode.Mult((a, b, ...))
     //Mode.Sngl(a, b, ...)
-    //Norm.dist(x, mean, standard_dev, cumulative)
-    //Norm.inv(probability, mean, standard_dev)
-    //Norm.s.dist(z, cumulative)
-    //Percentile(array, k)
+    /// NormDist(x, mean, standard_dev, cumulative). The normal density, or when cumulative is
+    /// true the probability of a value at most x.
+    NormDist(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    /// NormInv(probability, mean, standard_dev). The value with the given cumulative probability.
+    NormInv(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    /// NormSDist(z, cumulative). NormDist for the standard normal distribution.
+    NormSDist(Box<ConditionValue>, Box<ConditionValue>),
+    /// Percentile(array, k). Inclusive percentile, for k between 0 and 1.
+    Percentile(Vec<ConditionValue>, Box<ConditionValue>),
     //Percentile.inc(array, k)
     //Percentrank(array, x, significance)
     //Percentrank.exc(array, x, significance)
@@ -291,33 +611,153 @@ pub enum Fn {
     //Quartile(array, quart)
     //Quartile.exc(array, quart)
     //Quartile.inc(array, quart)
-    //Rank(number, ref, order)
+    /// Rank(number, ref, order). The position of the number among the numbers of ref, largest
+    /// first, or smallest first when order is not 0. Equal numbers share a rank.
+    Rank(
+        Box<ConditionValue>,
+        Vec<ConditionValue>,
+        Option<Box<ConditionValue>>,
+    ),
     //Rank.Avg(number, ref, order)
     //Rank.Eq(number, ref, order)
     //Skew(a, b, ...)
     //Skew.P(a, b, ...)
-    //Slope(know_ys, known_xs)
-    //Small(array, k)
-    //Standardize(x, mean, standard_dev)
-    //Stdev(a, b, ...)
+    /// Slope(known_ys, known_xs)
+    Slope(Vec<ConditionValue>, Vec<ConditionValue>),
+    /// Small(array, k). The k-th smallest number, counting from 1.
+    Small(Vec<ConditionValue>, Box<ConditionValue>),
+    /// Standardize(x, mean, standard_dev). The z-score of x.
+    Standardize(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    /// Stdev(a, b, ...). Sample standard deviation.
+    Stdev(Vec<ConditionValue>),
     //Stdev.p(a, b, ...)
     //Stdev.s(a, b, ...)
     //stdevA(a, b, ...)
-    //Stdevp(a, b, ...)
+    /// StdevP(a, b, ...). Population standard deviation.
+    StdevP(Vec<ConditionValue>),
     //Stdevpa(a, b, ...)
     //TrimMean(array, percent)
-    //var(a, b, ...)
+    /// Var(a, b, ...). Sample variance.
+    Var(Vec<ConditionValue>),
     //var.p(a, b, ...)
     //var.s(a, b, ...)
     //vara(a, b, ...)
-    //varp(a, b, ...)
+    /// VarP(a, b, ...). Population variance.
+    VarP(Vec<ConditionValue>),
     //varpa(a, b, ...)
 
+    // - Rollout -
+    // A value and a salt hash to a bucket from 0 to 99, so a feature can be rolled out to a
+    // stable share of users. The hash is FNV-1a over the UTF-8 text `salt:value`, with the value
+    // as text the way the text functions write it, mixed with the MurmurHash3 finalizer so that
+    // rollouts under different salts are independent. It is the same on every platform.
+    /// Bucket(value, salt). The bucket of the value, from 0 to 99.
+    Bucket(Box<ConditionValue>, Box<ConditionValue>),
+    /// Rollout(value, salt, percentage). True when the bucket of the value is less than the
+    /// percentage.
+    Rollout(
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+        Box<ConditionValue>,
+    ),
+    // - Validation -
+    // Validators are true when the value is text in the format and false otherwise, including
+    // when the value is missing, so they can be combined in a group like any other condition.
+    /// IsEmail(text). An address such as "jane.doe@example.com": a local part of letters,
+    /// digits, dots and `!#$%&'*+/=?^_`{|}~-`, and a domain of at least two labels. Quoted local
+    /// parts and IP address domains are not accepted.
+    IsEmail(Box<ConditionValue>),
+    /// IsIban(text). Two letters, two digits and up to 30 letters or digits, 15 to 34 characters
+    /// in all, whose check digits are valid. The country code and its length are not checked
+    /// against the IBAN registry. Spaces are ignored.
+    IsIban(Box<ConditionValue>),
+    /// IsLuhn(text). A number that passes the Luhn check, such as a credit card number. Spaces
+    /// and hyphens are ignored.
+    IsLuhn(Box<ConditionValue>),
+    /// IsPhoneNumber(text). An E.164 phone number: a plus sign and up to 15 digits, such as
+    /// "+14155550100".
+    IsPhoneNumber(Box<ConditionValue>),
+    /// IsUuid(text). A UUID of any version in the hyphenated form, such as
+    /// "123e4567-e89b-12d3-a456-426614174000".
+    IsUuid(Box<ConditionValue>),
+
     // - web -
-    //encodeUrl(text)
+    // URLs are parsed offline as browsers parse them, so the scheme and host are lowercase and
+    // text without a scheme, such as "example.com/path", is not a URL.
+    /// DecodeUrl(text). Decodes percent-encoded bytes. Text that does not decode to UTF-8 has
+    /// no value.
+    DecodeUrl(Box<ConditionValue>),
+    /// EncodeUrl(text). Percent-encodes every byte except letters, digits and `-_.~`.
+    EncodeUrl(Box<ConditionValue>),
     //filterxml(xml, xpath)
+    /// UrlHost(url). The host, such as "www.example.com". A URL without a host has no value.
+    UrlHost(Box<ConditionValue>),
+    /// UrlPath(url). The path, still percent-encoded, such as "/a%20b".
+    UrlPath(Box<ConditionValue>),
+    /// UrlQueryParameter(url, name). The decoded value of the first query parameter with the
+    /// name.
+    UrlQueryParameter(Box<ConditionValue>, Box<ConditionValue>),
+    /// UrlScheme(url). The scheme, such as "https".
+    UrlScheme(Box<ConditionValue>),
     //webservice(url)
 
     // - Database -
     // NA?
 }
+
+/// Dates excluded by the business day functions.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Holidays {
+    /// A calendar registered with the evaluation environment.
+    Calendar(String),
+    /// An array of ISO-8601 dates in the facts.
+    Json(ReferenceValue),
+}
+
+/// The rows searched by the lookup functions, keyed by their first column.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Table {
+    /// A table registered with the evaluation environment.
+    Named(String),
+    /// Rows kept with the condition.
+    Rows(Vec<Vec<FactValue>>),
+}
+
+/// How `Reduce` combines its values.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Reduction {
+    Sum,
+    Min,
+    Max,
+}
+
+/// A regular expression. It is compiled the first time it is used and kept with the condition,
+/// so evaluating the condition again, or for every item of an array, reuses it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Pattern {
+    pub source: String,
+    #[serde(skip)]
+    compiled: OnceLock<Option<Regex>>,
+}
+
+impl Pattern {
+    pub fn new(source: &str) -> Pattern {
+        Pattern {
+            source: source.to_string(),
+            compiled: OnceLock::new(),
+        }
+    }
+
+    /// The compiled expression, or `None` when the source is not a valid regular expression.
+    pub fn regex(&self) -> Option<&Regex> {
+        return self
+            .compiled
+            .get_or_init(|| Regex::new(&self.source).ok())
+            .as_ref();
+    }
+}
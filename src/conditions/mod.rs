@@ -6,32 +6,44 @@ use serde::{Deserialize, Serialize};
 pub mod functions;
 pub mod operators;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum Condition {
     Group(ConditionGroup),
     Single(SingleCondition),
     Array(ArrayCondition),
+    Values(ValuesCondition),
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ConditionGroup {
     pub operator: AggregationOperator,
     pub conditions: Vec<Condition>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SingleCondition {
     pub operator: ComparisonOperator,
     pub values: (ConditionValue, ConditionValue),
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ArrayCondition {
     pub operator: AggregationOperator,
     pub array: ReferenceValue,
     pub condition: Box<Condition>,
 }
 
+/// An array condition over a list of values, such as the result of `Split`, rather than an
+/// array in the facts. Within the condition, `item[]` is the current value, where `item` is a
+/// name chosen for the items. A fact with the same name can't be read within the condition.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValuesCondition {
+    pub operator: AggregationOperator,
+    pub values: ConditionValue,
+    pub item: String,
+    pub condition: Box<Condition>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ConditionValue {
     Fixed(FactValue),
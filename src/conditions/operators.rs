@@ -1,17 +1,22 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum AggregationOperator {
     Every,
     Any,
     None,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ComparisonOperator {
     Equals,
     GreaterThan,
     GreaterThanEqualTo,
     LessThan,
     LessThanEqualTo,
+    /// The first value is one of the items of the second, which must be an array.
+    In,
+    /// The first value is not one of the items of the second, which must be an array. Like `In`,
+    /// it is false when the first value is missing.
+    NotIn,
 }
@@ -0,0 +1,112 @@
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+
+use crate::facts::FactValue;
+
+/// Everything outside of the facts that a condition may depend on.
+pub struct Environment {
+    pub clock: Box<dyn Clock>,
+    /// Named holiday lists used by the business day functions.
+    pub holiday_calendars: HashMap<String, HashSet<NaiveDate>>,
+    /// Named tables used by the lookup functions, as rows keyed by their first column.
+    pub lookup_tables: HashMap<String, Vec<Vec<FactValue>>>,
+    /// Source of the numbers drawn by `Rand()` and `RandBetween()`.
+    pub random: Box<dyn Random>,
+}
+
+impl Environment {
+    pub fn new() -> Environment {
+        Environment {
+            clock: Box::new(SystemClock),
+            holiday_calendars: HashMap::new(),
+            lookup_tables: HashMap::new(),
+            random: Box::new(SeededRandom::from_clock()),
+        }
+    }
+
+    pub fn register_holiday_calendar(&mut self, name: &str, holidays: Vec<NaiveDate>) {
+        self.holiday_calendars
+            .insert(name.to_string(), holidays.into_iter().collect());
+    }
+
+    pub fn register_lookup_table(&mut self, name: &str, rows: Vec<Vec<FactValue>>) {
+        self.lookup_tables.insert(name.to_string(), rows);
+    }
+
+    /// Draw random numbers from `seed`, so that an evaluation can be replayed exactly.
+    pub fn seed_random(&mut self, seed: u64) {
+        self.random = Box::new(SeededRandom::new(seed));
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Environment::new()
+    }
+}
+
+/// Source of the current time for `Now()` and `Today()`.
+pub trait Clock {
+    fn now(&self) -> DateTime<FixedOffset>;
+}
+
+/// Reads the system time, in UTC.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        return Utc::now().into();
+    }
+}
+
+/// Always returns the same instant. Useful for replaying and testing rules.
+pub struct FixedClock {
+    pub now: DateTime<FixedOffset>,
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        return self.now;
+    }
+}
+
+/// Source of random numbers for `Rand()` and `RandBetween()`.
+pub trait Random {
+    /// The next of a sequence of uniformly distributed 64-bit numbers.
+    fn next_u64(&self) -> u64;
+}
+
+/// SplitMix64. The same seed gives the same numbers on every platform, including
+/// webassembly.
+pub struct SeededRandom {
+    state: Cell<u64>,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> SeededRandom {
+        SeededRandom {
+            state: Cell::new(seed),
+        }
+    }
+
+    /// Seeded from the system time, for evaluations that need not be replayed.
+    pub fn from_clock() -> SeededRandom {
+        let now = Utc::now();
+        let seed = (now.timestamp() as u64) << 32 ^ u64::from(now.timestamp_subsec_nanos());
+        return SeededRandom::new(seed);
+    }
+}
+
+impl Random for SeededRandom {
+    fn next_u64(&self) -> u64 {
+        let state = self.state.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+        self.state.set(state);
+
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        return z ^ (z >> 31);
+    }
+}
@@ -0,0 +1,171 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_function::get_integer_from_condition_value;
+use super::{evaluate_condition, evaluate_for_array_items};
+use super::{get_fact_from_condition_value, get_facts_from_condition_values, get_item_contexts};
+use crate::conditions::operators::AggregationOperator;
+use crate::conditions::{Condition, ConditionValue, ReferenceValue};
+use crate::facts::{FactValue, GetFact};
+
+pub fn evaluate_all<'a>(
+    array: &ReferenceValue,
+    condition: &Condition,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let all = evaluate_for_array_items(
+        &AggregationOperator::Every,
+        &array.address,
+        condition,
+        facts,
+        array_context,
+        environment,
+    );
+    return Some(FactValue::Boolean(all));
+}
+
+pub fn evaluate_any<'a>(
+    array: &ReferenceValue,
+    condition: &Condition,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let any = evaluate_for_array_items(
+        &AggregationOperator::Any,
+        &array.address,
+        condition,
+        facts,
+        array_context,
+        environment,
+    );
+    return Some(FactValue::Boolean(any));
+}
+
+pub fn evaluate_filter<'a>(
+    array: &ReferenceValue,
+    condition: &Condition,
+    value: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let item_address = format!("{}[]", array.address);
+    let mut items = Vec::new();
+
+    for item_context in get_item_contexts(&array.address, facts, array_context) {
+        if !evaluate_condition(condition, facts, &item_context, environment) {
+            continue;
+        }
+
+        let item = match value {
+            Some(value) => get_fact_from_condition_value(value, facts, &item_context, environment),
+            None => facts.get_fact(&item_address, &item_context),
+        };
+        if let Some(item) = item {
+            items.push(item);
+        }
+    }
+
+    return Some(FactValue::Array(items));
+}
+
+pub fn evaluate_map<'a>(
+    array: &ReferenceValue,
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let items = get_item_contexts(&array.address, facts, array_context)
+        .filter_map(|item_context| {
+            get_fact_from_condition_value(value, facts, &item_context, environment)
+        })
+        .collect();
+
+    return Some(FactValue::Array(items));
+}
+
+pub fn evaluate_sort<'a>(
+    values: &Vec<ConditionValue>,
+    order: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let descending = match order {
+        Some(order) => {
+            match get_integer_from_condition_value(order, facts, array_context, environment)? {
+                1 => false,
+                -1 => true,
+                _ => return None,
+            }
+        }
+        None => false,
+    };
+
+    let mut items = get_facts_from_condition_values(values, facts, array_context, environment);
+    items.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    if descending {
+        items.reverse();
+    }
+
+    return Some(FactValue::Array(items));
+}
+
+pub fn evaluate_unique<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let items = get_facts_from_condition_values(values, facts, array_context, environment);
+    return Some(FactValue::Array(distinct(items)));
+}
+
+pub fn evaluate_contains<'a>(
+    values: &Vec<ConditionValue>,
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let value = get_fact_from_condition_value(value, facts, array_context, environment)?;
+    let items = get_facts_from_condition_values(values, facts, array_context, environment);
+
+    return Some(FactValue::Boolean(items.contains(&value)));
+}
+
+/// The distinct values of a that are in b, or that are not in b when `in_b` is false.
+pub fn evaluate_set_comparison<'a>(
+    a: &Vec<ConditionValue>,
+    b: &Vec<ConditionValue>,
+    in_b: bool,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let a = get_facts_from_condition_values(a, facts, array_context, environment);
+    let b = get_facts_from_condition_values(b, facts, array_context, environment);
+
+    let items = a
+        .into_iter()
+        .filter(|item| b.contains(item) == in_b)
+        .collect();
+    return Some(FactValue::Array(distinct(items)));
+}
+
+/// Remove repeated values, keeping the first of each. Values aren't hashable, as floats and
+/// integers can be equal, so this compares every pair.
+fn distinct(values: Vec<FactValue>) -> Vec<FactValue> {
+    let mut items: Vec<FactValue> = Vec::new();
+    for value in values {
+        if !items.contains(&value) {
+            items.push(value);
+        }
+    }
+    return items;
+}
@@ -0,0 +1,217 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate};
+
+use super::environment::Environment;
+use super::evaluate_date_function::{add_days, get_date_from_condition_value};
+use super::evaluate_function::get_integer_from_condition_value;
+use super::{get_fact_from_condition_value, get_item_contexts};
+use crate::conditions::{functions::Holidays, ConditionValue};
+use crate::facts::{DateFact, FactValue, GetFact, NumberFact};
+
+/// Weekend days, indexed from Monday.
+type Weekend = [bool; 7];
+
+const SATURDAY_SUNDAY: Weekend = [false, false, false, false, false, true, true];
+
+pub fn evaluate_net_work_days<'a>(
+    start: &ConditionValue,
+    end: &ConditionValue,
+    weekend: &Option<Box<ConditionValue>>,
+    holidays: &Option<Holidays>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let start = get_date_from_condition_value(start, facts, array_context, environment)?;
+    let end = get_date_from_condition_value(end, facts, array_context, environment)?;
+    let weekend = get_weekend(weekend, facts, array_context, environment)?;
+    let holidays = get_holidays(holidays, facts, array_context, environment)?;
+
+    let work_days = count_work_days(
+        start.naive_local().date(),
+        end.naive_local().date(),
+        &weekend,
+        &holidays,
+    )?;
+    return Some(FactValue::Number(NumberFact::Integer(work_days)));
+}
+
+pub fn evaluate_work_day<'a>(
+    start: &ConditionValue,
+    days: &ConditionValue,
+    weekend: &Option<Box<ConditionValue>>,
+    holidays: &Option<Holidays>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let start = get_date_from_condition_value(start, facts, array_context, environment)?;
+    let days = get_integer_from_condition_value(days, facts, array_context, environment)?;
+    let weekend = get_weekend(weekend, facts, array_context, environment)?;
+    let holidays = get_holidays(holidays, facts, array_context, environment)?;
+
+    let date = add_work_days(start.naive_local().date(), days, &weekend, &holidays)?;
+    return Some(FactValue::Date(DateFact::Date(date)));
+}
+
+/// Weekends follow Excel's Intl functions: either a number (1 to 7 for pairs of days starting
+/// with Saturday and Sunday, 11 to 17 for single days starting with Sunday) or a seven character
+/// mask such as "0000011" where each 1 marks a weekend day, starting on Monday.
+fn get_weekend<'a>(
+    weekend: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<Weekend> {
+    let value = match weekend {
+        Some(value) => value,
+        None => return Some(SATURDAY_SUNDAY),
+    };
+
+    let mut weekend = [false; 7];
+    match get_fact_from_condition_value(value, facts, array_context, environment)? {
+        FactValue::String(mask) => {
+            if mask.chars().count() != 7 {
+                return None;
+            }
+            for (index, day) in mask.chars().enumerate() {
+                match day {
+                    '0' => {}
+                    '1' => weekend[index] = true,
+                    _ => return None,
+                }
+            }
+        }
+        FactValue::Number(_) => {
+            match get_integer_from_condition_value(value, facts, array_context, environment)? {
+                // Saturday is index 5, so pair n starts on day (n + 4) mod 7.
+                code @ 1..=7 => {
+                    weekend[((code + 4) % 7) as usize] = true;
+                    weekend[((code + 5) % 7) as usize] = true;
+                }
+                code @ 11..=17 => weekend[((code - 5) % 7) as usize] = true,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    }
+
+    if weekend.iter().all(|is_weekend| *is_weekend) {
+        return None;
+    }
+
+    return Some(weekend);
+}
+
+fn get_holidays<'a, 'b>(
+    holidays: &Option<Holidays>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &'b Environment,
+) -> Option<Cow<'b, HashSet<NaiveDate>>> {
+    match holidays {
+        None => Some(Cow::Owned(HashSet::new())),
+        Some(Holidays::Calendar(name)) => {
+            let calendar = environment.holiday_calendars.get(name)?;
+            Some(Cow::Borrowed(calendar))
+        }
+        Some(Holidays::Json(reference)) => {
+            let item_address = format!("{}[]", reference.address);
+
+            let mut dates = HashSet::new();
+            for item_context in get_item_contexts(&reference.address, facts, array_context) {
+                let date = match facts.get_fact(&item_address, &item_context)? {
+                    FactValue::Date(date) => date,
+                    FactValue::String(text) => DateFact::parse(&text)?,
+                    _ => return None,
+                };
+                dates.insert(date.naive_local().date());
+            }
+
+            Some(Cow::Owned(dates))
+        }
+    }
+}
+
+fn is_work_day(date: NaiveDate, weekend: &Weekend, holidays: &HashSet<NaiveDate>) -> bool {
+    let day_index = date.weekday().num_days_from_monday() as usize;
+    return !weekend[day_index] && !holidays.contains(&date);
+}
+
+/// Inclusive count of work days between two dates, negative when `end` is before `start`.
+fn count_work_days(
+    start: NaiveDate,
+    end: NaiveDate,
+    weekend: &Weekend,
+    holidays: &HashSet<NaiveDate>,
+) -> Option<i64> {
+    let (from, to, sign) = if start <= end {
+        (start, end, 1)
+    } else {
+        (end, start, -1)
+    };
+
+    // Whole weeks always contain the same number of work days, so only the remainder and the
+    // holidays need to be looked at individually.
+    let total_days = (to - from).num_days() + 1;
+    let work_days_per_week = weekend.iter().filter(|is_weekend| !**is_weekend).count() as i64;
+    let mut work_days = total_days / 7 * work_days_per_week;
+
+    let no_holidays = HashSet::new();
+    for offset in 0..total_days % 7 {
+        if is_work_day(add_days(to, -offset)?, weekend, &no_holidays) {
+            work_days += 1;
+        }
+    }
+
+    work_days -= holidays
+        .iter()
+        .filter(|holiday| from <= **holiday && **holiday <= to)
+        .filter(|holiday| is_work_day(**holiday, weekend, &no_holidays))
+        .count() as i64;
+
+    return Some(sign * work_days);
+}
+
+fn add_work_days(
+    start: NaiveDate,
+    days: i64,
+    weekend: &Weekend,
+    holidays: &HashSet<NaiveDate>,
+) -> Option<NaiveDate> {
+    let step = if days < 0 { -1 } else { 1 };
+    let work_days_per_week = weekend.iter().filter(|is_weekend| !**is_weekend).count() as u64;
+    let no_holidays = HashSet::new();
+    let mut remaining = days.unsigned_abs();
+    let mut date = start;
+
+    while remaining > 0 {
+        // Jump over whole weeks, keeping at least one work day to step to so the result lands
+        // on a work day. Holidays in the skipped weeks are work days still owed.
+        let weeks = (remaining - 1) / work_days_per_week;
+        if weeks > 0 {
+            let next = add_days(date, i64::try_from(weeks).ok()?.checked_mul(7 * step)?)?;
+            let is_skipped = |holiday: &NaiveDate| match step {
+                1 => date < *holiday && *holiday <= next,
+                _ => next <= *holiday && *holiday < date,
+            };
+            let skipped_holidays = holidays
+                .iter()
+                .filter(|holiday| is_skipped(holiday))
+                .filter(|holiday| is_work_day(**holiday, weekend, &no_holidays))
+                .count() as u64;
+            remaining = remaining - weeks * work_days_per_week + skipped_holidays;
+            date = next;
+            continue;
+        }
+
+        date = add_days(date, step)?;
+        if is_work_day(date, weekend, holidays) {
+            remaining -= 1;
+        }
+    }
+
+    return Some(date);
+}
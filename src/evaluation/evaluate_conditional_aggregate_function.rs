@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_condition;
+use super::evaluate_statistical_function::mean;
+use super::{get_fact_from_condition_value, get_item_contexts};
+use crate::conditions::{Condition, ConditionValue, ReferenceValue};
+use crate::facts::{FactValue, GetFact, NumberFact};
+
+/// Evaluate the criteria for each item of an array, as an array condition does, and resolve
+/// `value` within every matching item. Matching items without the value are skipped.
+fn get_matching_facts<'a>(
+    array: &ReferenceValue,
+    criteria: &Condition,
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Vec<FactValue> {
+    let mut matching_facts = Vec::new();
+
+    for item_context in get_item_contexts(&array.address, facts, array_context) {
+        if !evaluate_condition(criteria, facts, &item_context, environment) {
+            continue;
+        }
+        if let Some(fact) = get_fact_from_condition_value(value, facts, &item_context, environment)
+        {
+            matching_facts.push(fact);
+        }
+    }
+
+    return matching_facts;
+}
+
+fn get_matching_numbers<'a>(
+    array: &ReferenceValue,
+    criteria: &Condition,
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<Vec<NumberFact>> {
+    let mut numbers = Vec::new();
+
+    for fact in get_matching_facts(array, criteria, value, facts, array_context, environment) {
+        match fact {
+            FactValue::Number(number) => numbers.push(number),
+            _ => return None,
+        }
+    }
+
+    return Some(numbers);
+}
+
+pub fn evaluate_average_if<'a>(
+    array: &ReferenceValue,
+    criteria: &Condition,
+    average_value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_matching_numbers(
+        array,
+        criteria,
+        average_value,
+        facts,
+        array_context,
+        environment,
+    )?;
+
+    let average = mean(&numbers)?;
+    return Some(FactValue::Number(NumberFact::from_f64(average)?));
+}
+
+pub fn evaluate_count_if<'a>(
+    array: &ReferenceValue,
+    criteria: &Condition,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let count = get_item_contexts(&array.address, facts, array_context)
+        .filter(|item_context| evaluate_condition(criteria, facts, item_context, environment))
+        .count() as i64;
+
+    return Some(FactValue::Number(NumberFact::Integer(count)));
+}
+
+/// MaxIfs and MinIfs compare facts of any type, as `Max` and `Min` do, so they also find the
+/// latest or earliest of a set of dates.
+pub fn evaluate_max_ifs<'a>(
+    array: &ReferenceValue,
+    criteria: &Condition,
+    max_value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let mut max: Option<FactValue> = None;
+
+    for fact in get_matching_facts(
+        array,
+        criteria,
+        max_value,
+        facts,
+        array_context,
+        environment,
+    ) {
+        let fact = Some(fact);
+        if max.is_none() || fact > max {
+            max = fact;
+        }
+    }
+
+    return max;
+}
+
+pub fn evaluate_min_ifs<'a>(
+    array: &ReferenceValue,
+    criteria: &Condition,
+    min_value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let mut min: Option<FactValue> = None;
+
+    for fact in get_matching_facts(
+        array,
+        criteria,
+        min_value,
+        facts,
+        array_context,
+        environment,
+    ) {
+        let fact = Some(fact);
+        if min.is_none() || fact < min {
+            min = fact;
+        }
+    }
+
+    return min;
+}
+
+pub fn evaluate_sum_if<'a>(
+    array: &ReferenceValue,
+    criteria: &Condition,
+    sum_value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_matching_numbers(
+        array,
+        criteria,
+        sum_value,
+        facts,
+        array_context,
+        environment,
+    )?;
+
+    let mut sum = NumberFact::Integer(0);
+    for number in numbers {
+        sum = sum.checked_add(&number)?;
+    }
+
+    return Some(FactValue::Number(sum));
+}
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_information_function::date_serial_number;
+use super::evaluate_text_function::{fact_to_text, get_text_from_condition_value};
+use super::get_fact_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{DateFact, FactValue, GetFact, NumberFact};
+
+pub fn evaluate_value<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = match get_fact_from_condition_value(text, facts, array_context, environment)? {
+        FactValue::Number(number) => number,
+        FactValue::Date(date) => date_serial_number(&date)?,
+        FactValue::String(text) => parse_number(&text, '.', ',')?,
+        FactValue::Boolean(_) | FactValue::Array(_) => return None,
+    };
+
+    return Some(FactValue::Number(number));
+}
+
+pub fn evaluate_number_value<'a>(
+    text: &ConditionValue,
+    decimal_separator: &Option<Box<ConditionValue>>,
+    group_separator: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let decimal_separator =
+        get_separator(decimal_separator, '.', facts, array_context, environment)?;
+    let group_separator = get_separator(group_separator, ',', facts, array_context, environment)?;
+    if decimal_separator == group_separator {
+        return None;
+    }
+
+    let number = parse_number(&text, decimal_separator, group_separator)?;
+    return Some(FactValue::Number(number));
+}
+
+pub fn evaluate_text<'a>(
+    value: &ConditionValue,
+    format_text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let value = get_fact_from_condition_value(value, facts, array_context, environment)?;
+    let format_text =
+        get_text_from_condition_value(format_text, facts, array_context, environment)?;
+
+    let text = match value {
+        FactValue::Number(number) => format_number(number, &format_text)?,
+        FactValue::Date(date) => format_date(&date, &format_text)?,
+        _ => fact_to_text(&value),
+    };
+
+    return Some(FactValue::String(text));
+}
+
+pub fn evaluate_to_boolean<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let boolean = match get_fact_from_condition_value(value, facts, array_context, environment)? {
+        FactValue::Boolean(boolean) => boolean,
+        FactValue::Number(number) => number.as_f64() != 0.0,
+        FactValue::String(text) => match text.trim().to_lowercase().as_str() {
+            "true" | "yes" | "1" => true,
+            "false" | "no" | "0" => false,
+            _ => return None,
+        },
+        FactValue::Date(_) | FactValue::Array(_) => return None,
+    };
+
+    return Some(FactValue::Boolean(boolean));
+}
+
+/// Resolve a separator for `NumberValue`: the first character of the text, or `default`.
+fn get_separator<'a>(
+    separator: &Option<Box<ConditionValue>>,
+    default: char,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<char> {
+    match separator {
+        Some(value) => get_text_from_condition_value(value, facts, array_context, environment)?
+            .chars()
+            .next(),
+        None => Some(default),
+    }
+}
+
+/// Parse a number as `NumberValue` does. Whitespace is ignored, group separators may only
+/// appear before the decimal separator, and each trailing percent sign divides by 100. Whole
+/// numbers are integers.
+fn parse_number(text: &str, decimal_separator: char, group_separator: char) -> Option<NumberFact> {
+    let mut chars = text.chars().filter(|c| !c.is_whitespace()).peekable();
+
+    let mut normalized = String::new();
+    if let Some(sign) = chars.next_if(|c| *c == '-' || *c == '+') {
+        normalized.push(sign);
+    }
+
+    let mut has_digits = false;
+    let mut is_integer = true;
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == group_separator) {
+        if c != group_separator {
+            normalized.push(c);
+            has_digits = true;
+        }
+    }
+    if chars.next_if_eq(&decimal_separator).is_some() {
+        normalized.push('.');
+        is_integer = false;
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+            normalized.push(c);
+            has_digits = true;
+        }
+    }
+    if !has_digits {
+        return None;
+    }
+    if let Some(e) = chars.next_if(|c| *c == 'e' || *c == 'E') {
+        normalized.push(e);
+        is_integer = false;
+        if let Some(sign) = chars.next_if(|c| *c == '-' || *c == '+') {
+            normalized.push(sign);
+        }
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+            normalized.push(c);
+        }
+    }
+
+    let mut percentages = 0;
+    while chars.next_if_eq(&'%').is_some() {
+        percentages += 1;
+    }
+    if chars.next().is_some() {
+        return None;
+    }
+
+    if is_integer && percentages == 0 {
+        if let Ok(integer) = normalized.parse::<i64>() {
+            return Some(NumberFact::Integer(integer));
+        }
+    }
+    let number = normalized.parse::<f64>().ok()? / 100_f64.powi(percentages);
+    return NumberFact::from_f64(number);
+}
+
+/// Format a number with an Excel number format such as `#,##0.00`, `0%` or `$0.0`. The
+/// placeholders `0` and `#` are a required and an optional digit, a `,` among them groups
+/// thousands, and text around them is written as is. Halves round away from zero.
+fn format_number(number: NumberFact, format_text: &str) -> Option<String> {
+    let (prefix, placeholders, suffix) = split_number_format(format_text)?;
+    let percent = prefix.contains('%') || suffix.contains('%');
+
+    let (integer_placeholders, decimal_placeholders) = match placeholders.split_once('.') {
+        Some((integer, decimal)) => (integer, decimal),
+        None => (placeholders.as_str(), ""),
+    };
+    if decimal_placeholders.contains(',') {
+        return None;
+    }
+    let grouped = integer_placeholders.contains(',');
+    let min_integer_digits = integer_placeholders.matches('0').count();
+    let decimals = decimal_placeholders.len();
+    let min_decimals = decimal_placeholders.trim_end_matches('#').len();
+
+    let scale = if percent { 100 } else { 1 };
+    let (negative, digits) = match number {
+        NumberFact::Integer(value) => (
+            value < 0,
+            format!(
+                "{}{}",
+                i128::from(value).abs() * scale,
+                "0".repeat(decimals)
+            ),
+        ),
+        NumberFact::Float(value) => {
+            let scaled = (value.abs() * scale as f64 * 10_f64.powi(decimals as i32)).round();
+            if !scaled.is_finite() {
+                return None;
+            }
+            (value < 0.0, format!("{:.0}", scaled))
+        }
+    };
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+
+    let (integer_digits, decimal_digits) = digits.split_at(digits.len() - decimals);
+    let integer_digits = integer_digits.trim_start_matches('0');
+    let integer_digits = format!("{:0>width$}", integer_digits, width = min_integer_digits);
+    let mut decimal_digits = decimal_digits.to_string();
+    while decimal_digits.len() > min_decimals && decimal_digits.ends_with('0') {
+        decimal_digits.pop();
+    }
+
+    let mut text = String::new();
+    if negative && digits.chars().any(|digit| digit != '0') {
+        text.push('-');
+    }
+    text.push_str(&prefix);
+    if grouped {
+        text.push_str(&group_thousands(&integer_digits));
+    } else {
+        text.push_str(&integer_digits);
+    }
+    if !decimal_placeholders.is_empty() {
+        text.push('.');
+        text.push_str(&decimal_digits);
+    }
+    text.push_str(&suffix);
+
+    return Some(text);
+}
+
+/// Split a number format into the text before the placeholders, the placeholders and the text
+/// after them. Quoted text and backslash-escaped characters are literal.
+fn split_number_format(format_text: &str) -> Option<(String, String, String)> {
+    let mut prefix = String::new();
+    let mut placeholders = String::new();
+    let mut suffix = String::new();
+
+    for (literal, c) in parse_literals(format_text) {
+        let is_placeholder = !literal && matches!(c, '0' | '#' | ',' | '.');
+        if is_placeholder && suffix.is_empty() {
+            placeholders.push(c);
+        } else if placeholders.is_empty() {
+            prefix.push(c);
+        } else {
+            suffix.push(c);
+        }
+    }
+
+    if !placeholders.contains(['0', '#'])
+        || placeholders.starts_with(',')
+        || placeholders.ends_with(',')
+    {
+        return None;
+    }
+    return Some((prefix, placeholders, suffix));
+}
+
+fn group_thousands(digits: &str) -> String {
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    return grouped;
+}
+
+/// Format a date with an Excel date format such as `yyyy-mm-dd` or `d mmm yyyy hh:mm`. `m`
+/// and `mm` are minutes after an hour or before a second, and months otherwise.
+fn format_date(date: &DateFact, format_text: &str) -> Option<String> {
+    let chars = parse_literals(format_text);
+
+    let mut tokens: Vec<(bool, String)> = Vec::new();
+    for (literal, c) in chars {
+        let c = if literal { c } else { c.to_ascii_lowercase() };
+        match tokens.last_mut() {
+            Some((false, token)) if !literal && "ymdhs".contains(c) && token.starts_with(c) => {
+                token.push(c)
+            }
+            _ => tokens.push((literal || !"ymdhs".contains(c), c.to_string())),
+        }
+    }
+
+    let mut chrono_format = String::new();
+    for (i, (literal, token)) in tokens.iter().enumerate() {
+        if *literal {
+            chrono_format.push_str(&token.replace('%', "%%"));
+            continue;
+        }
+
+        let is_minutes = token.starts_with('m')
+            && token.len() <= 2
+            && (tokens[..i]
+                .iter()
+                .rev()
+                .find(|(literal, _)| !literal)
+                .is_some_and(|(_, previous)| previous.starts_with('h'))
+                || tokens[i + 1..]
+                    .iter()
+                    .find(|(literal, _)| !literal)
+                    .is_some_and(|(_, next)| next.starts_with('s')));
+
+        let specifier = match token.as_str() {
+            "yy" => "%y",
+            "yyyy" => "%Y",
+            "m" if is_minutes => "%-M",
+            "mm" if is_minutes => "%M",
+            "m" => "%-m",
+            "mm" => "%m",
+            "mmm" => "%b",
+            "mmmm" => "%B",
+            "d" => "%-d",
+            "dd" => "%d",
+            "ddd" => "%a",
+            "dddd" => "%A",
+            "h" => "%-H",
+            "hh" => "%H",
+            "s" => "%-S",
+            "ss" => "%S",
+            _ => return None,
+        };
+        chrono_format.push_str(specifier);
+    }
+
+    return Some(date.naive_local().format(&chrono_format).to_string());
+}
+
+/// The characters of a format, each marked as literal when quoted or escaped by a backslash.
+fn parse_literals(format_text: &str) -> Vec<(bool, char)> {
+    let mut chars = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for c in format_text.chars() {
+        if escaped {
+            chars.push((true, c));
+            escaped = false;
+        } else if c == '"' {
+            quoted = !quoted;
+        } else if c == '\\' && !quoted {
+            escaped = true;
+        } else {
+            chars.push((quoted, c));
+        }
+    }
+    return chars;
+}
@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, Months, NaiveDate, Timelike, Weekday};
+
+use super::environment::Environment;
+use super::evaluate_function::{get_integer_from_condition_value, get_string_from_condition_value};
+use super::get_fact_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{DateFact, FactValue, GetFact, NumberFact};
+
+/// Resolve a value as a date. ISO-8601 strings are accepted as well as date facts.
+pub(super) fn get_date_from_condition_value<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<DateFact> {
+    match get_fact_from_condition_value(value, facts, array_context, environment)? {
+        FactValue::Date(date) => Some(date),
+        FactValue::String(text) => DateFact::parse(&text),
+        _ => None,
+    }
+}
+
+pub fn evaluate_date<'a>(
+    year: &ConditionValue,
+    month: &ConditionValue,
+    day: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let year = get_integer_from_condition_value(year, facts, array_context, environment)?;
+    let month = get_integer_from_condition_value(month, facts, array_context, environment)?;
+    let day = get_integer_from_condition_value(day, facts, array_context, environment)?;
+
+    let first_of_year = NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, 1, 1)?;
+    let first_of_month = add_months(first_of_year, month.checked_sub(1)?)?;
+    let date = add_days(first_of_month, day.checked_sub(1)?)?;
+
+    return Some(FactValue::Date(DateFact::Date(date)));
+}
+
+pub fn evaluate_date_dif<'a>(
+    start: &ConditionValue,
+    end: &ConditionValue,
+    unit: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let start = get_date_from_condition_value(start, facts, array_context, environment)?;
+    let end = get_date_from_condition_value(end, facts, array_context, environment)?;
+    let unit = get_string_from_condition_value(unit, facts, array_context, environment)?;
+
+    let start = start.naive_local().date();
+    let end = end.naive_local().date();
+    if start > end {
+        return None;
+    }
+
+    let months = whole_months_between(start, end);
+    let difference = match unit.to_uppercase().as_str() {
+        "Y" => months / 12,
+        "M" => months,
+        "D" => (end - start).num_days(),
+        "YM" => months % 12,
+        "MD" => (end - add_months(start, months)?).num_days(),
+        "YD" => (end - add_months(start, months / 12 * 12)?).num_days(),
+        _ => return None,
+    };
+
+    return Some(FactValue::Number(NumberFact::Integer(difference)));
+}
+
+pub fn evaluate_date_value<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(text, facts, array_context, environment)?;
+    return Some(FactValue::Date(date));
+}
+
+pub fn evaluate_day<'a>(
+    date: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    return Some(integer_fact(date.naive_local().day() as i64));
+}
+
+pub fn evaluate_days<'a>(
+    end: &ConditionValue,
+    start: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let end = get_date_from_condition_value(end, facts, array_context, environment)?;
+    let start = get_date_from_condition_value(start, facts, array_context, environment)?;
+
+    let days = end.naive_local().date() - start.naive_local().date();
+    return Some(integer_fact(days.num_days()));
+}
+
+pub fn evaluate_days_360<'a>(
+    start: &ConditionValue,
+    end: &ConditionValue,
+    european_method: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let start = get_date_from_condition_value(start, facts, array_context, environment)?;
+    let end = get_date_from_condition_value(end, facts, array_context, environment)?;
+    let european_method = match european_method {
+        Some(value) => {
+            match get_fact_from_condition_value(value, facts, array_context, environment)? {
+                FactValue::Boolean(european_method) => european_method,
+                _ => return None,
+            }
+        }
+        None => false,
+    };
+
+    let days = days_360(
+        start.naive_local().date(),
+        end.naive_local().date(),
+        european_method,
+    );
+    return Some(integer_fact(days));
+}
+
+pub fn evaluate_edate<'a>(
+    date: &ConditionValue,
+    months: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    let months = get_integer_from_condition_value(months, facts, array_context, environment)?;
+
+    let date = add_months(date.naive_local().date(), months)?;
+    return Some(FactValue::Date(DateFact::Date(date)));
+}
+
+pub fn evaluate_eomonth<'a>(
+    date: &ConditionValue,
+    months: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    let months = get_integer_from_condition_value(months, facts, array_context, environment)?;
+
+    let first_of_month = date.naive_local().date().with_day(1)?;
+    let first_of_next_month = add_months(first_of_month, months.checked_add(1)?)?;
+    let end_of_month = first_of_next_month.pred_opt()?;
+    return Some(FactValue::Date(DateFact::Date(end_of_month)));
+}
+
+pub fn evaluate_hour<'a>(
+    date: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    return Some(integer_fact(date.naive_local().hour() as i64));
+}
+
+pub fn evaluate_iso_week_num<'a>(
+    date: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    return Some(integer_fact(date.naive_local().iso_week().week() as i64));
+}
+
+pub fn evaluate_minute<'a>(
+    date: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    return Some(integer_fact(date.naive_local().minute() as i64));
+}
+
+pub fn evaluate_month<'a>(
+    date: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    return Some(integer_fact(date.naive_local().month() as i64));
+}
+
+pub fn evaluate_now(environment: &Environment) -> Option<FactValue> {
+    return Some(FactValue::Date(DateFact::Timestamp(
+        environment.clock.now(),
+    )));
+}
+
+pub fn evaluate_second<'a>(
+    date: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    return Some(integer_fact(date.naive_local().second() as i64));
+}
+
+pub fn evaluate_today(environment: &Environment) -> Option<FactValue> {
+    let today = environment.clock.now().date_naive();
+    return Some(FactValue::Date(DateFact::Date(today)));
+}
+
+/// Return types follow Excel: 1 counts Sunday = 1 to Saturday = 7, 2 counts Monday = 1 to
+/// Sunday = 7, 3 counts Monday = 0 to Sunday = 6 and 11 to 17 count from Monday to Sunday = 1.
+pub fn evaluate_week_day<'a>(
+    date: &ConditionValue,
+    return_type: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    let return_type = match return_type {
+        Some(value) => get_integer_from_condition_value(value, facts, array_context, environment)?,
+        None => 1,
+    };
+
+    let days_from_monday = date.naive_local().weekday().num_days_from_monday() as i64;
+    let week_day = match return_type {
+        1 => (days_from_monday + 1) % 7 + 1,
+        2 => days_from_monday + 1,
+        3 => days_from_monday,
+        11..=17 => (days_from_monday - (return_type - 11)).rem_euclid(7) + 1,
+        _ => return None,
+    };
+
+    return Some(integer_fact(week_day));
+}
+
+/// Return types follow Excel: 1 and 17 start weeks on Sunday, 2 and 11 on Monday, 12 to 16 on
+/// Tuesday to Saturday and 21 gives the ISO week number.
+pub fn evaluate_week_num<'a>(
+    date: &ConditionValue,
+    return_type: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    let return_type = match return_type {
+        Some(value) => get_integer_from_condition_value(value, facts, array_context, environment)?,
+        None => 1,
+    };
+
+    let date = date.naive_local().date();
+    let week_start = match return_type {
+        1 | 17 => Weekday::Sun,
+        2 | 11 => Weekday::Mon,
+        12 => Weekday::Tue,
+        13 => Weekday::Wed,
+        14 => Weekday::Thu,
+        15 => Weekday::Fri,
+        16 => Weekday::Sat,
+        21 => return Some(integer_fact(date.iso_week().week() as i64)),
+        _ => return None,
+    };
+
+    let first_of_year = date.with_ordinal(1)?;
+    let offset = (first_of_year.weekday().num_days_from_monday() as i64
+        - week_start.num_days_from_monday() as i64)
+        .rem_euclid(7);
+    let week = (date.ordinal0() as i64 + offset) / 7 + 1;
+
+    return Some(integer_fact(week));
+}
+
+pub fn evaluate_year<'a>(
+    date: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let date = get_date_from_condition_value(date, facts, array_context, environment)?;
+    return Some(integer_fact(date.naive_local().year() as i64));
+}
+
+/// Bases follow Excel: 0 is US (NASD) 30/360, 1 actual/actual, 2 actual/360, 3 actual/365
+/// and 4 European 30/360.
+pub fn evaluate_year_frac<'a>(
+    start: &ConditionValue,
+    end: &ConditionValue,
+    basis: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let start = get_date_from_condition_value(start, facts, array_context, environment)?;
+    let end = get_date_from_condition_value(end, facts, array_context, environment)?;
+    let basis = match basis {
+        Some(value) => get_integer_from_condition_value(value, facts, array_context, environment)?,
+        None => 0,
+    };
+
+    let mut start = start.naive_local().date();
+    let mut end = end.naive_local().date();
+    if start > end {
+        std::mem::swap(&mut start, &mut end);
+    }
+
+    let actual_days = (end - start).num_days() as f64;
+    let year_frac = match basis {
+        0 => us_days_360_with_february(start, end) as f64 / 360.0,
+        1 => actual_days / actual_year_length(start, end),
+        2 => actual_days / 360.0,
+        3 => actual_days / 365.0,
+        4 => days_360(start, end, true) as f64 / 360.0,
+        _ => return None,
+    };
+
+    return Some(FactValue::Number(NumberFact::Float(year_frac)));
+}
+
+fn integer_fact(value: i64) -> FactValue {
+    return FactValue::Number(NumberFact::Integer(value));
+}
+
+pub(super) fn add_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    let magnitude = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months < 0 {
+        return date.checked_sub_months(magnitude);
+    }
+    return date.checked_add_months(magnitude);
+}
+
+pub(super) fn add_days(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    let magnitude = Days::new(days.unsigned_abs());
+    if days < 0 {
+        return date.checked_sub_days(magnitude);
+    }
+    return date.checked_add_days(magnitude);
+}
+
+/// Number of whole months from `start` to `end`, where `start <= end`.
+fn whole_months_between(start: NaiveDate, end: NaiveDate) -> i64 {
+    let mut months =
+        (end.year() as i64 - start.year() as i64) * 12 + end.month() as i64 - start.month() as i64;
+    if end.day() < start.day() {
+        months -= 1;
+    }
+    return months;
+}
+
+fn days_360(start: NaiveDate, end: NaiveDate, european_method: bool) -> i64 {
+    let mut start_day = start.day() as i64;
+    let mut end_day = end.day() as i64;
+
+    if start_day == 31 {
+        start_day = 30;
+    }
+    if end_day == 31 && (european_method || start_day == 30) {
+        end_day = 30;
+    }
+
+    return days_360_from_parts(start, start_day, end, end_day);
+}
+
+/// The NASD 30/360 count used by `YearFrac` basis 0, which also treats the last day of
+/// February as the 30th.
+fn us_days_360_with_february(start: NaiveDate, end: NaiveDate) -> i64 {
+    let mut start_day = start.day() as i64;
+    let mut end_day = end.day() as i64;
+
+    let start_is_end_of_february =
+        start.month() == 2 && start.succ_opt().map(|d| d.month()) == Some(3);
+    let end_is_end_of_february = end.month() == 2 && end.succ_opt().map(|d| d.month()) == Some(3);
+
+    if start_is_end_of_february && end_is_end_of_february {
+        end_day = 30;
+    }
+    if start_is_end_of_february {
+        start_day = 30;
+    }
+    if end_day == 31 && start_day >= 30 {
+        end_day = 30;
+    }
+    if start_day == 31 {
+        start_day = 30;
+    }
+
+    return days_360_from_parts(start, start_day, end, end_day);
+}
+
+fn days_360_from_parts(start: NaiveDate, start_day: i64, end: NaiveDate, end_day: i64) -> i64 {
+    return (end.year() as i64 - start.year() as i64) * 360
+        + (end.month() as i64 - start.month() as i64) * 30
+        + (end_day - start_day);
+}
+
+/// Denominator for actual/actual, where `start <= end`.
+fn actual_year_length(start: NaiveDate, end: NaiveDate) -> f64 {
+    let within_a_year = match add_months(start, 12) {
+        Some(one_year_later) => end <= one_year_later,
+        None => false,
+    };
+
+    if within_a_year {
+        let spans_leap_day =
+            (start.year()..=end.year()).any(|year| match NaiveDate::from_ymd_opt(year, 2, 29) {
+                Some(leap_day) => start <= leap_day && leap_day <= end,
+                None => false,
+            });
+        let in_leap_year = start.year() == end.year() && start.leap_year();
+
+        if spans_leap_day || in_leap_year {
+            return 366.0;
+        }
+        return 365.0;
+    }
+
+    let total_days: i64 = (start.year()..=end.year())
+        .map(|year| {
+            if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+                366
+            } else {
+                365
+            }
+        })
+        .sum();
+    return total_days as f64 / (end.year() - start.year() + 1) as f64;
+}
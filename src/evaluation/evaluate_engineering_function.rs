@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_function::{get_integer_from_condition_value, get_number_from_condition_value};
+use super::evaluate_text_function::get_text_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{FactValue, GetFact, NumberFact};
+
+/// The number bases of the conversion functions. Binary, octal and hexadecimal numbers are
+/// text of at most `MAX_DIGITS` digits.
+pub enum Base {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+const MAX_DIGITS: u32 = 10;
+
+impl Base {
+    fn radix(&self) -> u32 {
+        match self {
+            Base::Binary => 2,
+            Base::Octal => 8,
+            Base::Decimal => 10,
+            Base::Hexadecimal => 16,
+        }
+    }
+
+    /// The width of a two's complement number of `MAX_DIGITS` digits.
+    fn bits(&self) -> u32 {
+        match self {
+            Base::Binary => MAX_DIGITS,
+            Base::Octal => 3 * MAX_DIGITS,
+            Base::Decimal => 64,
+            Base::Hexadecimal => 4 * MAX_DIGITS,
+        }
+    }
+}
+
+/// Resolve a value for the bitwise functions, which only take non-negative integers.
+fn get_bits_from_condition_value<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<i64> {
+    match get_number_from_condition_value(value, facts, array_context, environment)? {
+        NumberFact::Integer(bits) if bits >= 0 => Some(bits),
+        _ => None,
+    }
+}
+
+pub fn evaluate_base_conversion<'a>(
+    number: &ConditionValue,
+    from: Base,
+    to: Base,
+    places: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let value = match from {
+        Base::Decimal => {
+            get_integer_from_condition_value(number, facts, array_context, environment)?
+        }
+        _ => {
+            let digits = get_text_from_condition_value(number, facts, array_context, environment)?;
+            parse_digits(&digits, &from)?
+        }
+    };
+    let places = match places {
+        Some(value) => Some(get_integer_from_condition_value(
+            value,
+            facts,
+            array_context,
+            environment,
+        )?),
+        None => None,
+    };
+
+    match to {
+        Base::Decimal => Some(FactValue::Number(NumberFact::Integer(value))),
+        _ => Some(FactValue::String(format_digits(value, &to, places)?)),
+    }
+}
+
+pub fn evaluate_bit_and<'a>(
+    a: &ConditionValue,
+    b: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let a = get_bits_from_condition_value(a, facts, array_context, environment)?;
+    let b = get_bits_from_condition_value(b, facts, array_context, environment)?;
+
+    return Some(FactValue::Number(NumberFact::Integer(a & b)));
+}
+
+pub fn evaluate_bit_or<'a>(
+    a: &ConditionValue,
+    b: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let a = get_bits_from_condition_value(a, facts, array_context, environment)?;
+    let b = get_bits_from_condition_value(b, facts, array_context, environment)?;
+
+    return Some(FactValue::Number(NumberFact::Integer(a | b)));
+}
+
+pub fn evaluate_bit_xor<'a>(
+    a: &ConditionValue,
+    b: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let a = get_bits_from_condition_value(a, facts, array_context, environment)?;
+    let b = get_bits_from_condition_value(b, facts, array_context, environment)?;
+
+    return Some(FactValue::Number(NumberFact::Integer(a ^ b)));
+}
+
+/// Shift left, or right when `shift_left` is false. Shifting set bits out of the left of a
+/// 63-bit number has no value.
+pub fn evaluate_bit_shift<'a>(
+    number: &ConditionValue,
+    shift_amount: &ConditionValue,
+    shift_left: bool,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_bits_from_condition_value(number, facts, array_context, environment)?;
+    let shift_amount =
+        get_integer_from_condition_value(shift_amount, facts, array_context, environment)?;
+
+    let left_shift = if shift_left {
+        shift_amount
+    } else {
+        shift_amount.checked_neg()?
+    };
+
+    let shifted = if left_shift >= 0 {
+        if number == 0 {
+            0
+        } else {
+            let left_shift = u32::try_from(left_shift).ok().filter(|shift| *shift < 63)?;
+            let shifted = number << left_shift;
+            if shifted < 0 || shifted >> left_shift != number {
+                return None;
+            }
+            shifted
+        }
+    } else {
+        match u32::try_from(left_shift.unsigned_abs()) {
+            Ok(right_shift) if right_shift < 64 => number >> right_shift,
+            _ => 0,
+        }
+    };
+
+    return Some(FactValue::Number(NumberFact::Integer(shifted)));
+}
+
+/// Parse binary, octal or hexadecimal digits. Ten digits with the top bit set are a negative
+/// number in two's complement.
+fn parse_digits(digits: &str, base: &Base) -> Option<i64> {
+    let digits = digits.trim();
+    if digits.chars().count() > MAX_DIGITS as usize
+        || !digits.chars().all(|digit| digit.is_digit(base.radix()))
+    {
+        return None;
+    }
+    if digits.is_empty() {
+        return Some(0);
+    }
+
+    let value = i64::from_str_radix(digits, base.radix()).ok()?;
+    if value >= 1 << (base.bits() - 1) {
+        return Some(value - (1 << base.bits()));
+    }
+    return Some(value);
+}
+
+/// Format a number as binary, octal or hexadecimal digits, zero-padded to `places`. Negative
+/// numbers are written as ten digits of two's complement, ignoring places.
+fn format_digits(value: i64, base: &Base, places: Option<i64>) -> Option<String> {
+    let limit: i64 = 1 << (base.bits() - 1);
+    if value < -limit || value >= limit {
+        return None;
+    }
+
+    if value < 0 {
+        return Some(to_radix(value + 2 * limit, base));
+    }
+
+    let digits = to_radix(value, base);
+    match places {
+        Some(places) => {
+            let places = usize::try_from(places).ok()?;
+            if places < digits.len() || places > MAX_DIGITS as usize {
+                return None;
+            }
+            Some(format!("{:0>width$}", digits, width = places))
+        }
+        None => Some(digits),
+    }
+}
+
+fn to_radix(value: i64, base: &Base) -> String {
+    match base {
+        Base::Binary => format!("{:b}", value),
+        Base::Octal => format!("{:o}", value),
+        Base::Decimal => value.to_string(),
+        Base::Hexadecimal => format!("{:X}", value),
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_function::get_number_from_condition_value;
+use super::evaluate_math_function::get_numbers_from_condition_values;
+use super::get_optional_facts_from_condition_values;
+use crate::conditions::ConditionValue;
+use crate::facts::{DateFact, FactValue, GetFact, NumberFact};
+
+/// The most iterations the rate solvers take before giving up. Excel gives up after 20 for
+/// `Rate`, but `XIrr` with irregular dates can need more steps from the default guess, and a
+/// result is only accepted once it is within the tolerance however many steps it took.
+const MAX_ITERATIONS: usize = 100;
+
+/// Solvers stop once a step changes the rate by less than this.
+const TOLERANCE: f64 = 1e-10;
+
+/// The guess used by the rate solvers when none is given, as in Excel.
+const DEFAULT_GUESS: f64 = 0.1;
+
+fn get_optional_number<'a>(
+    value: &Option<Box<ConditionValue>>,
+    default: f64,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<f64> {
+    match value {
+        Some(value) => {
+            let number = get_number_from_condition_value(value, facts, array_context, environment)?;
+            Some(number.as_f64())
+        }
+        None => Some(default),
+    }
+}
+
+/// Resolve the payment type, where 0 means payments are due at the end of each period and 1 at
+/// the start.
+fn get_payments_at_start<'a>(
+    payment_type: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<bool> {
+    let payment_type = get_optional_number(payment_type, 0.0, facts, array_context, environment)?;
+
+    if payment_type == 0.0 {
+        return Some(false);
+    }
+    if payment_type == 1.0 {
+        return Some(true);
+    }
+    return None;
+}
+
+fn get_number<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<f64> {
+    let number = get_number_from_condition_value(value, facts, array_context, environment)?;
+    return Some(number.as_f64());
+}
+
+fn float_fact(value: f64) -> Option<FactValue> {
+    return Some(FactValue::Number(NumberFact::from_f64(value)?));
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_fv<'a>(
+    rate: &ConditionValue,
+    nper: &ConditionValue,
+    pmt: &ConditionValue,
+    pv: &Option<Box<ConditionValue>>,
+    payment_type: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let rate = get_number(rate, facts, array_context, environment)?;
+    let nper = get_number(nper, facts, array_context, environment)?;
+    let pmt = get_number(pmt, facts, array_context, environment)?;
+    let pv = get_optional_number(pv, 0.0, facts, array_context, environment)?;
+    let at_start = get_payments_at_start(payment_type, facts, array_context, environment)?;
+
+    return float_fact(future_value(rate, nper, pmt, pv, at_start));
+}
+
+pub fn evaluate_irr<'a>(
+    values: &Vec<ConditionValue>,
+    guess: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let values: Vec<f64> =
+        get_numbers_from_condition_values(values, facts, array_context, environment)?
+            .iter()
+            .map(|value| value.as_f64())
+            .collect();
+    let guess = get_optional_number(guess, DEFAULT_GUESS, facts, array_context, environment)?;
+
+    if !has_positive_and_negative(&values) {
+        return None;
+    }
+
+    let irr = solve_rate(
+        |rate| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(period, value)| value / (1.0 + rate).powi(period as i32))
+                .sum()
+        },
+        guess,
+    )?;
+    return float_fact(irr);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_nper<'a>(
+    rate: &ConditionValue,
+    pmt: &ConditionValue,
+    pv: &ConditionValue,
+    fv: &Option<Box<ConditionValue>>,
+    payment_type: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let rate = get_number(rate, facts, array_context, environment)?;
+    let pmt = get_number(pmt, facts, array_context, environment)?;
+    let pv = get_number(pv, facts, array_context, environment)?;
+    let fv = get_optional_number(fv, 0.0, facts, array_context, environment)?;
+    let at_start = get_payments_at_start(payment_type, facts, array_context, environment)?;
+
+    if rate == 0.0 {
+        return float_fact(-(pv + fv) / pmt);
+    }
+
+    let payment = pmt * (1.0 + rate * payment_factor(at_start));
+    let nper = ((payment - fv * rate) / (payment + pv * rate)).ln() / (1.0 + rate).ln();
+    return float_fact(nper);
+}
+
+pub fn evaluate_npv<'a>(
+    rate: &ConditionValue,
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let rate = get_number(rate, facts, array_context, environment)?;
+    let values = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+
+    let npv: f64 = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| value.as_f64() / (1.0 + rate).powi(i as i32 + 1))
+        .sum();
+    return float_fact(npv);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_pmt<'a>(
+    rate: &ConditionValue,
+    nper: &ConditionValue,
+    pv: &ConditionValue,
+    fv: &Option<Box<ConditionValue>>,
+    payment_type: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let rate = get_number(rate, facts, array_context, environment)?;
+    let nper = get_number(nper, facts, array_context, environment)?;
+    let pv = get_number(pv, facts, array_context, environment)?;
+    let fv = get_optional_number(fv, 0.0, facts, array_context, environment)?;
+    let at_start = get_payments_at_start(payment_type, facts, array_context, environment)?;
+
+    if rate == 0.0 {
+        return float_fact(-(pv + fv) / nper);
+    }
+
+    let growth = (1.0 + rate).powf(nper);
+    let pmt =
+        -(pv * growth + fv) * rate / ((1.0 + rate * payment_factor(at_start)) * (growth - 1.0));
+    return float_fact(pmt);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_pv<'a>(
+    rate: &ConditionValue,
+    nper: &ConditionValue,
+    pmt: &ConditionValue,
+    fv: &Option<Box<ConditionValue>>,
+    payment_type: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let rate = get_number(rate, facts, array_context, environment)?;
+    let nper = get_number(nper, facts, array_context, environment)?;
+    let pmt = get_number(pmt, facts, array_context, environment)?;
+    let fv = get_optional_number(fv, 0.0, facts, array_context, environment)?;
+    let at_start = get_payments_at_start(payment_type, facts, array_context, environment)?;
+
+    if rate == 0.0 {
+        return float_fact(-(fv + pmt * nper));
+    }
+
+    let growth = (1.0 + rate).powf(nper);
+    let pv = -(fv + pmt * (1.0 + rate * payment_factor(at_start)) * (growth - 1.0) / rate) / growth;
+    return float_fact(pv);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_rate<'a>(
+    nper: &ConditionValue,
+    pmt: &ConditionValue,
+    pv: &ConditionValue,
+    fv: &Option<Box<ConditionValue>>,
+    payment_type: &Option<Box<ConditionValue>>,
+    guess: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let nper = get_number(nper, facts, array_context, environment)?;
+    let pmt = get_number(pmt, facts, array_context, environment)?;
+    let pv = get_number(pv, facts, array_context, environment)?;
+    let fv = get_optional_number(fv, 0.0, facts, array_context, environment)?;
+    let at_start = get_payments_at_start(payment_type, facts, array_context, environment)?;
+    let guess = get_optional_number(guess, DEFAULT_GUESS, facts, array_context, environment)?;
+
+    let rate = solve_rate(
+        |rate| fv - future_value(rate, nper, pmt, pv, at_start),
+        guess,
+    )?;
+    return float_fact(rate);
+}
+
+pub fn evaluate_xirr<'a>(
+    values: &Vec<ConditionValue>,
+    dates: &Vec<ConditionValue>,
+    guess: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let cash_flows = get_dated_cash_flows(values, dates, facts, array_context, environment)?;
+    let guess = get_optional_number(guess, DEFAULT_GUESS, facts, array_context, environment)?;
+
+    let values: Vec<f64> = cash_flows.iter().map(|(value, _)| *value).collect();
+    if !has_positive_and_negative(&values) {
+        return None;
+    }
+
+    let xirr = solve_rate(|rate| dated_present_value(rate, &cash_flows), guess)?;
+    return float_fact(xirr);
+}
+
+pub fn evaluate_xnpv<'a>(
+    rate: &ConditionValue,
+    values: &Vec<ConditionValue>,
+    dates: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let rate = get_number(rate, facts, array_context, environment)?;
+    let cash_flows = get_dated_cash_flows(values, dates, facts, array_context, environment)?;
+
+    if rate <= -1.0 {
+        return None;
+    }
+    return float_fact(dated_present_value(rate, &cash_flows));
+}
+
+/// Pair each cash flow with the number of years since the first date, item by item. The values
+/// and dates must be the same length, and a missing value or date makes the whole list invalid,
+/// as in Excel.
+fn get_dated_cash_flows<'a>(
+    values: &Vec<ConditionValue>,
+    dates: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<Vec<(f64, f64)>> {
+    let values =
+        get_optional_facts_from_condition_values(values, facts, array_context, environment);
+    let dates = get_optional_facts_from_condition_values(dates, facts, array_context, environment);
+    if values.is_empty() || values.len() != dates.len() {
+        return None;
+    }
+
+    let mut dated_values = Vec::with_capacity(values.len());
+    for (value, date) in values.into_iter().zip(dates) {
+        let value = match value? {
+            FactValue::Number(number) => number.as_f64(),
+            _ => return None,
+        };
+        let date = match date? {
+            FactValue::Date(date) => date,
+            FactValue::String(text) => DateFact::parse(&text)?,
+            _ => return None,
+        };
+        dated_values.push((value, date.naive_local().date()));
+    }
+
+    let first_day = dated_values[0].1;
+    let cash_flows = dated_values
+        .into_iter()
+        .map(|(value, day)| (value, (day - first_day).num_days() as f64 / 365.0))
+        .collect();
+    return Some(cash_flows);
+}
+
+fn dated_present_value(rate: f64, cash_flows: &[(f64, f64)]) -> f64 {
+    return cash_flows
+        .iter()
+        .map(|(value, years)| value / (1.0 + rate).powf(*years))
+        .sum();
+}
+
+fn future_value(rate: f64, nper: f64, pmt: f64, pv: f64, at_start: bool) -> f64 {
+    if rate == 0.0 {
+        return -(pv + pmt * nper);
+    }
+
+    let growth = (1.0 + rate).powf(nper);
+    return -(pv * growth + pmt * (1.0 + rate * payment_factor(at_start)) * (growth - 1.0) / rate);
+}
+
+fn payment_factor(at_start: bool) -> f64 {
+    if at_start {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn has_positive_and_negative(values: &[f64]) -> bool {
+    return values.iter().any(|value| *value > 0.0) && values.iter().any(|value| *value < 0.0);
+}
+
+/// Find the rate at which `f` is zero using Newton's method, starting from `guess`. There is
+/// no rate if the solver leaves the domain of rates above -100% or doesn't converge within
+/// `MAX_ITERATIONS`.
+fn solve_rate(f: impl Fn(f64) -> f64, guess: f64) -> Option<f64> {
+    let mut rate = guess;
+
+    for _ in 0..MAX_ITERATIONS {
+        let value = f(rate);
+        let step = 1e-6 * rate.abs().max(1e-3);
+        let derivative = (f(rate + step) - f(rate - step)) / (2.0 * step);
+        if !value.is_finite() || !derivative.is_finite() || derivative == 0.0 {
+            return None;
+        }
+
+        let next_rate = rate - value / derivative;
+        if !next_rate.is_finite() || next_rate <= -1.0 {
+            return None;
+        }
+        if (next_rate - rate).abs() < TOLERANCE {
+            return Some(next_rate);
+        }
+
+        rate = next_rate;
+    }
+
+    return None;
+}
@@ -1,25 +1,763 @@
 use std::collections::HashMap;
 
-use super::get_fact_from_condition_value;
-use crate::conditions::{functions::Fn, ConditionValue};
-use crate::facts::{FactValue, GetFact};
+use super::environment::Environment;
+use super::evaluate_array_function::*;
+use super::evaluate_business_day_function::*;
+use super::evaluate_conditional_aggregate_function::*;
+use super::evaluate_conversion_function::*;
+use super::evaluate_date_function::*;
+use super::evaluate_engineering_function::*;
+use super::evaluate_financial_function::*;
+use super::evaluate_information_function::*;
+use super::evaluate_logical_function::*;
+use super::evaluate_lookup_function::*;
+use super::evaluate_math_function::*;
+use super::evaluate_rollout_function::*;
+use super::evaluate_statistical_function::*;
+use super::evaluate_text_function::*;
+use super::evaluate_validation_function::*;
+use super::evaluate_web_function::*;
+use super::{get_fact_from_condition_value, get_facts_from_condition_values};
+use crate::conditions::{
+    functions::{Fn, Reduction},
+    ConditionValue,
+};
+use crate::facts::{FactValue, GetFact, NumberFact};
 
 pub fn evaluate_fn<'a>(
     function: &Fn,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     match function {
-        Fn::Min(values) => evaluate_min(values, facts, array_context),
-        Fn::Max(values) => evaluate_max(values, facts, array_context),
+        Fn::Min(values) => evaluate_min(values, facts, array_context, environment),
+        Fn::Max(values) => evaluate_max(values, facts, array_context, environment),
+        Fn::Average(values) => evaluate_average(values, facts, array_context, environment),
+        Fn::Count(values) => evaluate_count(values, facts, array_context, environment),
+        Fn::CountA(values) => evaluate_count_a(values, facts, array_context, environment),
+        Fn::Median(values) => evaluate_median(values, facts, array_context, environment),
+        Fn::Mode(values) => evaluate_mode(values, facts, array_context, environment),
+        Fn::Large(values, k) => evaluate_nth(values, k, true, facts, array_context, environment),
+        Fn::Small(values, k) => evaluate_nth(values, k, false, facts, array_context, environment),
+        Fn::Percentile(values, k) => {
+            evaluate_percentile(values, k, facts, array_context, environment)
+        }
+        Fn::Stdev(values) => evaluate_stdev(values, true, facts, array_context, environment),
+        Fn::StdevP(values) => evaluate_stdev(values, false, facts, array_context, environment),
+        Fn::Var(values) => evaluate_var(values, true, facts, array_context, environment),
+        Fn::VarP(values) => evaluate_var(values, false, facts, array_context, environment),
+
+        Fn::And(values) => evaluate_and(values, facts, array_context, environment),
+        Fn::Or(values) => evaluate_or(values, facts, array_context, environment),
+        Fn::Not(value) => evaluate_not(value, facts, array_context, environment),
+        Fn::Coalesce(values) => evaluate_coalesce(values, facts, array_context, environment),
+        Fn::If(test, true_value, false_value) => evaluate_if(
+            test,
+            true_value,
+            false_value,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::IfNull(value, fallback) => evaluate_coalesce(
+            [value.as_ref(), fallback.as_ref()],
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Switch(expression, cases, default) => evaluate_switch(
+            expression,
+            cases,
+            default,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::ToBoolean(value) => evaluate_to_boolean(value, facts, array_context, environment),
+        Fn::Xor(values) => evaluate_xor(values, facts, array_context, environment),
+
+        Fn::GreaterThan(values) => evaluate_greater_than(values, facts, array_context, environment),
+        Fn::LessThan(values) => evaluate_less_than(values, facts, array_context, environment),
+        Fn::Equal(values) => evaluate_equal(values, facts, array_context, environment),
+
+        Fn::Date(year, month, day) => {
+            evaluate_date(year, month, day, facts, array_context, environment)
+        }
+        Fn::DateDif(start, end, unit) => {
+            evaluate_date_dif(start, end, unit, facts, array_context, environment)
+        }
+        Fn::DateValue(text) => evaluate_date_value(text, facts, array_context, environment),
+        Fn::Day(date) => evaluate_day(date, facts, array_context, environment),
+        Fn::Days(end, start) => evaluate_days(end, start, facts, array_context, environment),
+        Fn::Days360(start, end, method) => {
+            evaluate_days_360(start, end, method, facts, array_context, environment)
+        }
+        Fn::EDate(date, months) => evaluate_edate(date, months, facts, array_context, environment),
+        Fn::EOMonth(date, months) => {
+            evaluate_eomonth(date, months, facts, array_context, environment)
+        }
+        Fn::Hour(date) => evaluate_hour(date, facts, array_context, environment),
+        Fn::ISOWeekNum(date) => evaluate_iso_week_num(date, facts, array_context, environment),
+        Fn::Minute(date) => evaluate_minute(date, facts, array_context, environment),
+        Fn::Month(date) => evaluate_month(date, facts, array_context, environment),
+        Fn::NetWorkDays(start, end, holidays) => evaluate_net_work_days(
+            start,
+            end,
+            &None,
+            holidays,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::NetWorkDaysIntl(start, end, weekend, holidays) => evaluate_net_work_days(
+            start,
+            end,
+            weekend,
+            holidays,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Now => evaluate_now(environment),
+        Fn::Second(date) => evaluate_second(date, facts, array_context, environment),
+        Fn::Today => evaluate_today(environment),
+        Fn::WeekDay(date, return_type) => {
+            evaluate_week_day(date, return_type, facts, array_context, environment)
+        }
+        Fn::WeekNum(date, return_type) => {
+            evaluate_week_num(date, return_type, facts, array_context, environment)
+        }
+        Fn::WorkDay(start, days, holidays) => evaluate_work_day(
+            start,
+            days,
+            &None,
+            holidays,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::WorkDayIntl(start, days, weekend, holidays) => evaluate_work_day(
+            start,
+            days,
+            weekend,
+            holidays,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Year(date) => evaluate_year(date, facts, array_context, environment),
+        Fn::YearFrac(start, end, basis) => {
+            evaluate_year_frac(start, end, basis, facts, array_context, environment)
+        }
+
+        Fn::AverageIf(array, criteria, average_value) => evaluate_average_if(
+            array,
+            criteria,
+            average_value,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::CountIf(array, criteria) => {
+            evaluate_count_if(array, criteria, facts, array_context, environment)
+        }
+        Fn::MaxIfs(array, criteria, max_value) => evaluate_max_ifs(
+            array,
+            criteria,
+            max_value,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::MinIfs(array, criteria, min_value) => evaluate_min_ifs(
+            array,
+            criteria,
+            min_value,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::SumIf(array, criteria, sum_value) => evaluate_sum_if(
+            array,
+            criteria,
+            sum_value,
+            facts,
+            array_context,
+            environment,
+        ),
+
+        Fn::All(array, condition) => {
+            evaluate_all(array, condition, facts, array_context, environment)
+        }
+        Fn::Any(array, condition) => {
+            evaluate_any(array, condition, facts, array_context, environment)
+        }
+        Fn::Contains(values, value) => {
+            evaluate_contains(values, value, facts, array_context, environment)
+        }
+        Fn::Difference(a, b) => {
+            evaluate_set_comparison(a, b, false, facts, array_context, environment)
+        }
+        Fn::Filter(array, condition, value) => {
+            evaluate_filter(array, condition, value, facts, array_context, environment)
+        }
+        Fn::Intersection(a, b) => {
+            evaluate_set_comparison(a, b, true, facts, array_context, environment)
+        }
+        Fn::Map(array, value) => evaluate_map(array, value, facts, array_context, environment),
+        Fn::Reduce(reduction, values) => match reduction {
+            Reduction::Sum => evaluate_sum(values, facts, array_context, environment),
+            Reduction::Min => evaluate_min(values, facts, array_context, environment),
+            Reduction::Max => evaluate_max(values, facts, array_context, environment),
+        },
+        Fn::Sort(values, order) => evaluate_sort(values, order, facts, array_context, environment),
+        Fn::Union(values) | Fn::Unique(values) => {
+            evaluate_unique(values, facts, array_context, environment)
+        }
+
+        Fn::Bin2Dec(number) => evaluate_base_conversion(
+            number,
+            Base::Binary,
+            Base::Decimal,
+            &None,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Bin2Hex(number, places) => evaluate_base_conversion(
+            number,
+            Base::Binary,
+            Base::Hexadecimal,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Bin2Oct(number, places) => evaluate_base_conversion(
+            number,
+            Base::Binary,
+            Base::Octal,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Dec2Bin(number, places) => evaluate_base_conversion(
+            number,
+            Base::Decimal,
+            Base::Binary,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Dec2Hex(number, places) => evaluate_base_conversion(
+            number,
+            Base::Decimal,
+            Base::Hexadecimal,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Dec2Oct(number, places) => evaluate_base_conversion(
+            number,
+            Base::Decimal,
+            Base::Octal,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Hex2Bin(number, places) => evaluate_base_conversion(
+            number,
+            Base::Hexadecimal,
+            Base::Binary,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Hex2Dec(number) => evaluate_base_conversion(
+            number,
+            Base::Hexadecimal,
+            Base::Decimal,
+            &None,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Hex2Oct(number, places) => evaluate_base_conversion(
+            number,
+            Base::Hexadecimal,
+            Base::Octal,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Oct2Bin(number, places) => evaluate_base_conversion(
+            number,
+            Base::Octal,
+            Base::Binary,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Oct2Dec(number) => evaluate_base_conversion(
+            number,
+            Base::Octal,
+            Base::Decimal,
+            &None,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Oct2Hex(number, places) => evaluate_base_conversion(
+            number,
+            Base::Octal,
+            Base::Hexadecimal,
+            places,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::BitAnd(a, b) => evaluate_bit_and(a, b, facts, array_context, environment),
+        Fn::BitLShift(number, shift_amount) => evaluate_bit_shift(
+            number,
+            shift_amount,
+            true,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::BitOr(a, b) => evaluate_bit_or(a, b, facts, array_context, environment),
+        Fn::BitRShift(number, shift_amount) => evaluate_bit_shift(
+            number,
+            shift_amount,
+            false,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::BitXOr(a, b) => evaluate_bit_xor(a, b, facts, array_context, environment),
+
+        Fn::ACos(number) => {
+            evaluate_real_function(number, f64::acos, facts, array_context, environment)
+        }
+        Fn::ASin(number) => {
+            evaluate_real_function(number, f64::asin, facts, array_context, environment)
+        }
+        Fn::ATan(number) => {
+            evaluate_real_function(number, f64::atan, facts, array_context, environment)
+        }
+        Fn::Cos(number) => {
+            evaluate_real_function(number, f64::cos, facts, array_context, environment)
+        }
+        Fn::CosH(number) => {
+            evaluate_real_function(number, f64::cosh, facts, array_context, environment)
+        }
+        Fn::Cot(number) => evaluate_real_function(
+            number,
+            |angle| 1.0 / angle.tan(),
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Csc(number) => evaluate_real_function(
+            number,
+            |angle| 1.0 / angle.sin(),
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Degrees(number) => {
+            evaluate_real_function(number, f64::to_degrees, facts, array_context, environment)
+        }
+        Fn::Radians(number) => {
+            evaluate_real_function(number, f64::to_radians, facts, array_context, environment)
+        }
+        Fn::Sec(number) => evaluate_real_function(
+            number,
+            |angle| 1.0 / angle.cos(),
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Sin(number) => {
+            evaluate_real_function(number, f64::sin, facts, array_context, environment)
+        }
+        Fn::SinH(number) => {
+            evaluate_real_function(number, f64::sinh, facts, array_context, environment)
+        }
+        Fn::Tan(number) => {
+            evaluate_real_function(number, f64::tan, facts, array_context, environment)
+        }
+        Fn::TanH(number) => {
+            evaluate_real_function(number, f64::tanh, facts, array_context, environment)
+        }
+        Fn::ATan2(x, y) => evaluate_atan2(x, y, facts, array_context, environment),
+
+        Fn::Fv(rate, nper, pmt, pv, payment_type) => evaluate_fv(
+            rate,
+            nper,
+            pmt,
+            pv,
+            payment_type,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Irr(values, guess) => evaluate_irr(values, guess, facts, array_context, environment),
+        Fn::Nper(rate, pmt, pv, fv, payment_type) => evaluate_nper(
+            rate,
+            pmt,
+            pv,
+            fv,
+            payment_type,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Npv(rate, values) => evaluate_npv(rate, values, facts, array_context, environment),
+        Fn::Pmt(rate, nper, pv, fv, payment_type) => evaluate_pmt(
+            rate,
+            nper,
+            pv,
+            fv,
+            payment_type,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Pv(rate, nper, pmt, fv, payment_type) => evaluate_pv(
+            rate,
+            nper,
+            pmt,
+            fv,
+            payment_type,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Rate(nper, pmt, pv, fv, payment_type, guess) => evaluate_rate(
+            nper,
+            pmt,
+            pv,
+            fv,
+            payment_type,
+            guess,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Xirr(values, dates, guess) => {
+            evaluate_xirr(values, dates, guess, facts, array_context, environment)
+        }
+        Fn::Xnpv(rate, values, dates) => {
+            evaluate_xnpv(rate, values, dates, facts, array_context, environment)
+        }
 
-        Fn::And(values) => evaluate_and(values, facts, array_context),
-        Fn::Or(values) => evaluate_or(values, facts, array_context),
-        Fn::Not(value) => evaluate_not(value, facts, array_context),
+        Fn::IsBlank(value) => evaluate_is_blank(value, facts, array_context, environment),
+        Fn::IsLogical(value) => evaluate_is_logical(value, facts, array_context, environment),
+        Fn::IsNumber(value) => evaluate_is_number(value, facts, array_context, environment),
+        Fn::IsText(value) => evaluate_is_text(value, facts, array_context, environment),
+        Fn::N(value) => evaluate_n(value, facts, array_context, environment),
+        Fn::Type(value) => evaluate_type(value, facts, array_context, environment),
+
+        Fn::BinomDist(successes, trials, probability, cumulative) => evaluate_binom_dist(
+            successes,
+            trials,
+            probability,
+            cumulative,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Forecast(x, known_ys, known_xs) | Fn::ForecastLinear(x, known_ys, known_xs) => {
+            evaluate_forecast(x, known_ys, known_xs, facts, array_context, environment)
+        }
+        Fn::Intercept(known_ys, known_xs) => {
+            evaluate_intercept(known_ys, known_xs, facts, array_context, environment)
+        }
+        Fn::NormDist(x, mean, standard_dev, cumulative) => evaluate_norm_dist(
+            x,
+            mean,
+            standard_dev,
+            cumulative,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::NormInv(probability, mean, standard_dev) => evaluate_norm_inv(
+            probability,
+            mean,
+            standard_dev,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::NormSDist(z, cumulative) => {
+            evaluate_norm_s_dist(z, cumulative, facts, array_context, environment)
+        }
+        Fn::Rank(number, values, order) => {
+            evaluate_rank(number, values, order, facts, array_context, environment)
+        }
+        Fn::Slope(known_ys, known_xs) => {
+            evaluate_slope(known_ys, known_xs, facts, array_context, environment)
+        }
+        Fn::Standardize(x, mean, standard_dev) => {
+            evaluate_standardize(x, mean, standard_dev, facts, array_context, environment)
+        }
+        Fn::Rows(array) => evaluate_rows(array, facts, array_context),
+        Fn::VLookup(value, table, column, approximate) => evaluate_v_lookup(
+            value,
+            table,
+            column,
+            approximate,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::XLookup(value, table, column, if_not_found, match_mode) => evaluate_x_lookup(
+            value,
+            table,
+            column,
+            if_not_found,
+            match_mode,
+            facts,
+            array_context,
+            environment,
+        ),
+
+        Fn::Bucket(value, salt) => evaluate_bucket(value, salt, facts, array_context, environment),
+        Fn::Rollout(value, salt, percentage) => {
+            evaluate_rollout(value, salt, percentage, facts, array_context, environment)
+        }
 
-        Fn::GreaterThan(values) => evaluate_greater_than(values, facts, array_context),
-        Fn::LessThan(values) => evaluate_less_than(values, facts, array_context),
-        Fn::Equal(values) => evaluate_equal(values, facts, array_context),
+        Fn::IsEmail(value) => evaluate_is_email(value, facts, array_context, environment),
+        Fn::IsIban(value) => evaluate_is_iban(value, facts, array_context, environment),
+        Fn::IsLuhn(value) => evaluate_is_luhn(value, facts, array_context, environment),
+        Fn::IsPhoneNumber(value) => {
+            evaluate_is_phone_number(value, facts, array_context, environment)
+        }
+        Fn::IsUuid(value) => evaluate_is_uuid(value, facts, array_context, environment),
+
+        Fn::DecodeUrl(text) => evaluate_decode_url(text, facts, array_context, environment),
+        Fn::EncodeUrl(text) => evaluate_encode_url(text, facts, array_context, environment),
+        Fn::UrlHost(url) => evaluate_url_host(url, facts, array_context, environment),
+        Fn::UrlPath(url) => evaluate_url_path(url, facts, array_context, environment),
+        Fn::UrlQueryParameter(url, name) => {
+            evaluate_url_query_parameter(url, name, facts, array_context, environment)
+        }
+        Fn::UrlScheme(url) => evaluate_url_scheme(url, facts, array_context, environment),
+
+        Fn::Concat(values) => evaluate_concat(values, facts, array_context, environment),
+        Fn::Exact(a, b) => evaluate_exact(a, b, facts, array_context, environment),
+        Fn::Extract(text, pattern, group) => {
+            evaluate_extract(text, pattern, group, facts, array_context, environment)
+        }
+        Fn::Find(find, within, start_num) => evaluate_find(
+            find,
+            within,
+            start_num,
+            true,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Left(text, num_chars) => {
+            evaluate_left(text, num_chars, facts, array_context, environment)
+        }
+        Fn::Len(text) => evaluate_len(text, facts, array_context, environment),
+        Fn::Lower(text) => evaluate_lower(text, facts, array_context, environment),
+        Fn::Matches(text, pattern) => {
+            evaluate_matches(text, pattern, facts, array_context, environment)
+        }
+        Fn::Mid(text, start, num_chars) => {
+            evaluate_mid(text, start, num_chars, facts, array_context, environment)
+        }
+        Fn::NumberValue(text, decimal_separator, group_separator) => evaluate_number_value(
+            text,
+            decimal_separator,
+            group_separator,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Proper(text) => evaluate_proper(text, facts, array_context, environment),
+        Fn::Replace(old_text, start, num_chars, new_text) => evaluate_replace(
+            old_text,
+            start,
+            num_chars,
+            new_text,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Rept(text, num) => evaluate_rept(text, num, facts, array_context, environment),
+        Fn::Right(text, num_chars) => {
+            evaluate_right(text, num_chars, facts, array_context, environment)
+        }
+        Fn::Search(find, within, start_num) => evaluate_find(
+            find,
+            within,
+            start_num,
+            false,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Split(text, delimiter) => {
+            evaluate_split(text, delimiter, facts, array_context, environment)
+        }
+        Fn::Substitute(text, old, new, instance) => {
+            evaluate_substitute(text, old, new, instance, facts, array_context, environment)
+        }
+        Fn::Text(value, format_text) => {
+            evaluate_text(value, format_text, facts, array_context, environment)
+        }
+        Fn::TextJoin(delimiter, ignore_empty, values) => evaluate_text_join(
+            delimiter,
+            ignore_empty,
+            values,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Trim(text) => evaluate_trim(text, facts, array_context, environment),
+        Fn::Upper(text) => evaluate_upper(text, facts, array_context, environment),
+        Fn::Value(text) => evaluate_value(text, facts, array_context, environment),
+
+        Fn::Abs(number) => evaluate_abs(number, facts, array_context, environment),
+        Fn::Ceiling(number, significance) => {
+            evaluate_ceiling(number, significance, facts, array_context, environment)
+        }
+        Fn::Divide(numerator, denominator) => {
+            evaluate_divide(numerator, denominator, facts, array_context, environment)
+        }
+        Fn::Exp(number) => {
+            evaluate_real_function(number, f64::exp, facts, array_context, environment)
+        }
+        Fn::Floor(number, significance) => {
+            evaluate_floor(number, significance, facts, array_context, environment)
+        }
+        Fn::Int(number) => evaluate_int(number, facts, array_context, environment),
+        Fn::Ln(number) => {
+            evaluate_real_function(number, f64::ln, facts, array_context, environment)
+        }
+        Fn::Log(number, base) => evaluate_log(number, base, facts, array_context, environment),
+        Fn::Log10(number) => {
+            evaluate_real_function(number, f64::log10, facts, array_context, environment)
+        }
+        Fn::Mod(number, divisor) => {
+            evaluate_mod(number, divisor, facts, array_context, environment)
+        }
+        Fn::Pi => Some(FactValue::Number(NumberFact::Float(std::f64::consts::PI))),
+        Fn::Power(number, power) => {
+            evaluate_power(number, power, facts, array_context, environment)
+        }
+        Fn::Product(values) => evaluate_product(values, facts, array_context, environment),
+        Fn::Quotient(numerator, denominator) => {
+            evaluate_quotient(numerator, denominator, facts, array_context, environment)
+        }
+        Fn::Rand => evaluate_rand(environment),
+        Fn::RandBetween(bottom, top) => {
+            evaluate_rand_between(bottom, top, facts, array_context, environment)
+        }
+        Fn::Round(number, num_digits) => evaluate_round(
+            number,
+            Some(num_digits),
+            Rounding::Nearest,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::RoundDown(number, num_digits) => evaluate_round(
+            number,
+            Some(num_digits),
+            Rounding::Down,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::RoundUp(number, num_digits) => evaluate_round(
+            number,
+            Some(num_digits),
+            Rounding::Up,
+            facts,
+            array_context,
+            environment,
+        ),
+        Fn::Sign(number) => evaluate_sign(number, facts, array_context, environment),
+        Fn::Sqrt(number) => evaluate_sqrt(number, facts, array_context, environment),
+        Fn::Subtract(a, b) => evaluate_subtract(a, b, facts, array_context, environment),
+        Fn::Sum(values) => evaluate_sum(values, facts, array_context, environment),
+        Fn::Trunc(number, num_digits) => evaluate_round(
+            number,
+            num_digits.as_deref(),
+            Rounding::Down,
+            facts,
+            array_context,
+            environment,
+        ),
+    }
+}
+
+pub(super) fn get_number_from_condition_value<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<NumberFact> {
+    match get_fact_from_condition_value(value, facts, array_context, environment)? {
+        FactValue::Number(number) => Some(number),
+        _ => None,
+    }
+}
+
+/// Resolve a value as an integer, truncating floats.
+pub(super) fn get_integer_from_condition_value<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<i64> {
+    match get_fact_from_condition_value(value, facts, array_context, environment)? {
+        FactValue::Number(NumberFact::Integer(number)) => Some(number),
+        FactValue::Number(NumberFact::Float(number)) => {
+            if !number.is_finite() || number.abs() >= i64::MAX as f64 {
+                return None;
+            }
+            Some(number.trunc() as i64)
+        }
+        _ => None,
+    }
+}
+
+pub(super) fn get_boolean_from_condition_value<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<bool> {
+    match get_fact_from_condition_value(value, facts, array_context, environment)? {
+        FactValue::Boolean(boolean) => Some(boolean),
+        _ => None,
+    }
+}
+
+pub(super) fn get_string_from_condition_value<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<String> {
+    match get_fact_from_condition_value(value, facts, array_context, environment)? {
+        FactValue::String(text) => Some(text),
+        _ => None,
     }
 }
 
@@ -27,12 +765,13 @@ fn evaluate_min<'a>(
     values: &Vec<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     let mut min_value: Option<FactValue> = None;
 
-    for value in values {
-        let fact = get_fact_from_condition_value(value, facts, array_context);
-        if fact.is_some() && (min_value.is_none() || fact < min_value) {
+    for fact in get_facts_from_condition_values(values, facts, array_context, environment) {
+        let fact = Some(fact);
+        if min_value.is_none() || fact < min_value {
             min_value = fact;
         }
     }
@@ -44,12 +783,13 @@ fn evaluate_max<'a>(
     values: &Vec<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     let mut max_value: Option<FactValue> = None;
 
-    for value in values {
-        let fact = get_fact_from_condition_value(value, facts, array_context);
-        if fact.is_some() && (max_value.is_none() || fact > max_value) {
+    for fact in get_facts_from_condition_values(values, facts, array_context, environment) {
+        let fact = Some(fact);
+        if max_value.is_none() || fact > max_value {
             max_value = fact;
         }
     }
@@ -61,9 +801,10 @@ fn evaluate_and<'a>(
     values: &Vec<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     for value in values {
-        let fact = get_fact_from_condition_value(value, facts, array_context);
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
 
         match fact.unwrap_or(FactValue::Boolean(false)) {
             FactValue::Boolean(value) => {
@@ -82,9 +823,10 @@ fn evaluate_or<'a>(
     values: &Vec<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     for value in values {
-        let fact = get_fact_from_condition_value(value, facts, array_context);
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
 
         match fact.unwrap_or(FactValue::Boolean(false)) {
             FactValue::Boolean(value) => {
@@ -103,8 +845,9 @@ fn evaluate_not<'a>(
     value: &Box<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
-    let fact = get_fact_from_condition_value(value, facts, array_context);
+    let fact = get_fact_from_condition_value(value, facts, array_context, environment);
 
     if fact.is_none() {
         return None;
@@ -120,16 +863,17 @@ fn evaluate_greater_than<'a>(
     values: &Vec<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     if values.len() < 2 {
         return None;
     }
 
-    let first_fact = get_fact_from_condition_value(&values[0], facts, array_context);
+    let first_fact = get_fact_from_condition_value(&values[0], facts, array_context, environment);
 
     // Slightly inefficient since we compare the first value to itself.
     for value in values {
-        let fact = get_fact_from_condition_value(value, facts, array_context);
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
         if first_fact < fact {
             return Some(FactValue::Boolean(false));
         }
@@ -142,16 +886,17 @@ fn evaluate_less_than<'a>(
     values: &Vec<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     if values.len() < 2 {
         return None;
     }
 
-    let first_fact = get_fact_from_condition_value(&values[0], facts, array_context);
+    let first_fact = get_fact_from_condition_value(&values[0], facts, array_context, environment);
 
     // Slightly inefficient since we compare the first value to itself.
     for value in values {
-        let fact = get_fact_from_condition_value(value, facts, array_context);
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
         if first_fact > fact {
             return Some(FactValue::Boolean(false));
         }
@@ -164,16 +909,17 @@ fn evaluate_equal<'a>(
     values: &Vec<ConditionValue>,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     if values.len() < 2 {
         return None;
     }
 
-    let first_fact = get_fact_from_condition_value(&values[0], facts, array_context);
+    let first_fact = get_fact_from_condition_value(&values[0], facts, array_context, environment);
 
     // Slightly inefficient since we compare the first value to itself.
     for value in values {
-        let fact = get_fact_from_condition_value(value, facts, array_context);
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
         if first_fact != fact {
             return Some(FactValue::Boolean(false));
         }
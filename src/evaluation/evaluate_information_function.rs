@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use super::environment::Environment;
+use super::get_fact_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{DateFact, FactValue, GetFact, NumberFact};
+
+pub fn evaluate_is_blank<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let fact = get_fact_from_condition_value(value, facts, array_context, environment);
+    return Some(FactValue::Boolean(fact.is_none()));
+}
+
+pub fn evaluate_is_logical<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let fact = get_fact_from_condition_value(value, facts, array_context, environment);
+    let is_logical = matches!(fact, Some(FactValue::Boolean(_)));
+    return Some(FactValue::Boolean(is_logical));
+}
+
+pub fn evaluate_is_number<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let fact = get_fact_from_condition_value(value, facts, array_context, environment);
+    let is_number = matches!(fact, Some(FactValue::Number(_)));
+    return Some(FactValue::Boolean(is_number));
+}
+
+pub fn evaluate_is_text<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let fact = get_fact_from_condition_value(value, facts, array_context, environment);
+    let is_text = matches!(fact, Some(FactValue::String(_)));
+    return Some(FactValue::Boolean(is_text));
+}
+
+pub fn evaluate_n<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let n = match get_fact_from_condition_value(value, facts, array_context, environment) {
+        Some(FactValue::Number(number)) => number,
+        Some(FactValue::Boolean(true)) => NumberFact::Integer(1),
+        Some(FactValue::Date(date)) => date_serial_number(&date)?,
+        _ => NumberFact::Integer(0),
+    };
+
+    return Some(FactValue::Number(n));
+}
+
+pub fn evaluate_type<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let fact = get_fact_from_condition_value(value, facts, array_context, environment)?;
+
+    let type_number = match fact {
+        FactValue::Number(_) => 1,
+        FactValue::Date(_) => 1,
+        FactValue::String(_) => 2,
+        FactValue::Boolean(_) => 4,
+        FactValue::Array(_) => 64,
+    };
+
+    return Some(FactValue::Number(NumberFact::Integer(type_number)));
+}
+
+/// The Excel serial number of a date: days since 1899-12-30, with the time of day as a
+/// fraction. Whole dates are integers.
+pub(super) fn date_serial_number(date: &DateFact) -> Option<NumberFact> {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?;
+
+    match date {
+        DateFact::Date(date) => Some(NumberFact::Integer((*date - epoch).num_days())),
+        _ => {
+            let elapsed = date.naive_local() - epoch.and_hms_opt(0, 0, 0)?;
+            NumberFact::from_f64(elapsed.num_milliseconds() as f64 / 86_400_000.0)
+        }
+    }
+}
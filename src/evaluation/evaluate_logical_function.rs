@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::get_fact_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{FactValue, GetFact};
+
+pub fn evaluate_coalesce<'a, 'b>(
+    values: impl IntoIterator<Item = &'b ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    for value in values {
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
+        if fact.is_some() {
+            return fact;
+        }
+    }
+
+    return None;
+}
+
+pub fn evaluate_if<'a>(
+    test: &ConditionValue,
+    true_value: &ConditionValue,
+    false_value: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let test = get_fact_from_condition_value(test, facts, array_context, environment);
+
+    match test.unwrap_or(FactValue::Boolean(false)) {
+        FactValue::Boolean(true) => {
+            get_fact_from_condition_value(true_value, facts, array_context, environment)
+        }
+        FactValue::Boolean(false) => match false_value {
+            Some(false_value) => {
+                get_fact_from_condition_value(false_value, facts, array_context, environment)
+            }
+            None => Some(FactValue::Boolean(false)),
+        },
+        _ => None,
+    }
+}
+
+pub fn evaluate_switch<'a>(
+    expression: &ConditionValue,
+    cases: &Vec<(ConditionValue, ConditionValue)>,
+    default: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let expression = get_fact_from_condition_value(expression, facts, array_context, environment)?;
+
+    for (value, result) in cases {
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
+        if fact.as_ref() == Some(&expression) {
+            return get_fact_from_condition_value(result, facts, array_context, environment);
+        }
+    }
+
+    match default {
+        Some(default) => get_fact_from_condition_value(default, facts, array_context, environment),
+        None => None,
+    }
+}
+
+pub fn evaluate_xor<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let mut result = false;
+
+    for value in values {
+        let fact = get_fact_from_condition_value(value, facts, array_context, environment);
+        if let Some(FactValue::Boolean(true)) = fact {
+            result = !result;
+        }
+    }
+
+    return Some(FactValue::Boolean(result));
+}
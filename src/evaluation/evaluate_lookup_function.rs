@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::mem::discriminant;
+
+use super::environment::Environment;
+use super::evaluate_function::{
+    get_boolean_from_condition_value, get_integer_from_condition_value,
+};
+use super::get_fact_from_condition_value;
+use crate::conditions::{functions::Table, ConditionValue};
+use crate::facts::{FactValue, GetFact};
+
+pub fn evaluate_v_lookup<'a>(
+    value: &ConditionValue,
+    table: &Table,
+    column: &ConditionValue,
+    approximate: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let value = get_fact_from_condition_value(value, facts, array_context, environment)?;
+    let rows = get_rows(table, environment)?;
+    let column = get_column(column, facts, array_context, environment)?;
+    let approximate = match approximate {
+        Some(approximate) => {
+            get_boolean_from_condition_value(approximate, facts, array_context, environment)?
+        }
+        None => true,
+    };
+
+    let row = match approximate {
+        true => find_row(&value, rows, Ordering::Less)?,
+        false => find_row(&value, rows, Ordering::Equal)?,
+    };
+    return row.get(column).cloned();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_x_lookup<'a>(
+    value: &ConditionValue,
+    table: &Table,
+    column: &ConditionValue,
+    if_not_found: &Option<Box<ConditionValue>>,
+    match_mode: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let value = get_fact_from_condition_value(value, facts, array_context, environment)?;
+    let rows = get_rows(table, environment)?;
+    let column = get_column(column, facts, array_context, environment)?;
+    let closest = match match_mode {
+        Some(match_mode) => {
+            match get_integer_from_condition_value(match_mode, facts, array_context, environment)? {
+                0 => Ordering::Equal,
+                -1 => Ordering::Less,
+                1 => Ordering::Greater,
+                _ => return None,
+            }
+        }
+        None => Ordering::Equal,
+    };
+
+    match (find_row(&value, rows, closest), if_not_found) {
+        (Some(row), _) => row.get(column).cloned(),
+        (None, Some(if_not_found)) => {
+            get_fact_from_condition_value(if_not_found, facts, array_context, environment)
+        }
+        (None, None) => None,
+    }
+}
+
+fn get_rows<'b>(table: &'b Table, environment: &'b Environment) -> Option<&'b Vec<Vec<FactValue>>> {
+    match table {
+        Table::Named(name) => environment.lookup_tables.get(name),
+        Table::Rows(rows) => Some(rows),
+    }
+}
+
+/// The index of a column counted from 1.
+fn get_column<'a>(
+    column: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<usize> {
+    let column = get_integer_from_condition_value(column, facts, array_context, environment)?;
+    return usize::try_from(column).ok()?.checked_sub(1);
+}
+
+/// The first row keyed by the value or, failing that, the row of the closest key of the same
+/// type on the side of the value given by `closest`.
+fn find_row<'t>(
+    value: &FactValue,
+    rows: &'t [Vec<FactValue>],
+    closest: Ordering,
+) -> Option<&'t Vec<FactValue>> {
+    if let Some(row) = rows.iter().find(|row| row.first() == Some(value)) {
+        return Some(row);
+    }
+    if closest == Ordering::Equal {
+        return None;
+    }
+
+    let candidates = rows.iter().filter(|row| match row.first() {
+        Some(key) => {
+            discriminant(key) == discriminant(value) && key.partial_cmp(value) == Some(closest)
+        }
+        None => false,
+    });
+    let by_key = |a: &&Vec<FactValue>, b: &&Vec<FactValue>| {
+        a[0].partial_cmp(&b[0]).unwrap_or(Ordering::Equal)
+    };
+
+    match closest {
+        Ordering::Less => candidates.max_by(by_key),
+        _ => candidates.min_by(by_key),
+    }
+}
@@ -0,0 +1,494 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_function::{get_integer_from_condition_value, get_number_from_condition_value};
+use super::get_facts_from_condition_values;
+use crate::conditions::ConditionValue;
+use crate::facts::{FactValue, GetFact, NumberFact};
+
+/// Rounding modes for `Round`, `RoundDown` and `RoundUp`. Halves round away from zero, down
+/// is towards zero and up is away from zero.
+pub enum Rounding {
+    Nearest,
+    Down,
+    Up,
+}
+
+/// Resolve every number in a list of values, expanding array references. Missing facts are
+/// skipped, as with `Min` and `Max`, but a value of any other type makes the whole list invalid.
+pub(super) fn get_numbers_from_condition_values<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<Vec<NumberFact>> {
+    let mut numbers = Vec::with_capacity(values.len());
+
+    for fact in get_facts_from_condition_values(values, facts, array_context, environment) {
+        match fact {
+            FactValue::Number(number) => numbers.push(number),
+            _ => return None,
+        }
+    }
+
+    return Some(numbers);
+}
+
+pub fn evaluate_abs<'a>(
+    number: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+
+    let abs = match number {
+        NumberFact::Integer(value) => NumberFact::Integer(value.checked_abs()?),
+        NumberFact::Float(value) => NumberFact::Float(value.abs()),
+    };
+    return Some(FactValue::Number(abs));
+}
+
+pub fn evaluate_atan2<'a>(
+    x: &ConditionValue,
+    y: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let x = get_number_from_condition_value(x, facts, array_context, environment)?.as_f64();
+    let y = get_number_from_condition_value(y, facts, array_context, environment)?.as_f64();
+
+    // Excel has no angle for the origin.
+    if x == 0.0 && y == 0.0 {
+        return None;
+    }
+    return Some(FactValue::Number(NumberFact::from_f64(y.atan2(x))?));
+}
+
+pub fn evaluate_ceiling<'a>(
+    number: &ConditionValue,
+    significance: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+    let significance =
+        get_number_from_condition_value(significance, facts, array_context, environment)?;
+
+    let ceiling = round_to_multiple(number, significance, true)?;
+    return Some(FactValue::Number(ceiling));
+}
+
+pub fn evaluate_divide<'a>(
+    numerator: &ConditionValue,
+    denominator: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numerator = get_number_from_condition_value(numerator, facts, array_context, environment)?;
+    let denominator =
+        get_number_from_condition_value(denominator, facts, array_context, environment)?;
+
+    return Some(FactValue::Number(numerator.checked_div(&denominator)?));
+}
+
+pub fn evaluate_floor<'a>(
+    number: &ConditionValue,
+    significance: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+    let significance =
+        get_number_from_condition_value(significance, facts, array_context, environment)?;
+
+    let floor = round_to_multiple(number, significance, false)?;
+    return Some(FactValue::Number(floor));
+}
+
+pub fn evaluate_int<'a>(
+    number: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+
+    let int = match number {
+        NumberFact::Integer(value) => value,
+        NumberFact::Float(value) => float_to_integer(value.floor())?,
+    };
+    return Some(FactValue::Number(NumberFact::Integer(int)));
+}
+
+pub fn evaluate_log<'a>(
+    number: &ConditionValue,
+    base: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+    let base = match base {
+        Some(value) => get_number_from_condition_value(value, facts, array_context, environment)?,
+        None => NumberFact::Integer(10),
+    };
+
+    let log = number.as_f64().ln() / base.as_f64().ln();
+    return Some(FactValue::Number(NumberFact::from_f64(log)?));
+}
+
+pub fn evaluate_mod<'a>(
+    number: &ConditionValue,
+    divisor: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+    let divisor = get_number_from_condition_value(divisor, facts, array_context, environment)?;
+
+    let remainder = match (number, divisor) {
+        (NumberFact::Integer(number), NumberFact::Integer(divisor)) => {
+            let mut remainder = number.checked_rem(divisor)?;
+            if remainder != 0 && (remainder < 0) != (divisor < 0) {
+                remainder += divisor;
+            }
+            NumberFact::Integer(remainder)
+        }
+        _ => {
+            let number = number.as_f64();
+            let divisor = divisor.as_f64();
+            if divisor == 0.0 {
+                return None;
+            }
+            NumberFact::from_f64(number - divisor * (number / divisor).floor())?
+        }
+    };
+
+    return Some(FactValue::Number(remainder));
+}
+
+pub fn evaluate_power<'a>(
+    number: &ConditionValue,
+    power: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+    let power = get_number_from_condition_value(power, facts, array_context, environment)?;
+
+    let result = match (number, power) {
+        (NumberFact::Integer(number), NumberFact::Integer(power)) if power >= 0 => {
+            NumberFact::Integer(number.checked_pow(u32::try_from(power).ok()?)?)
+        }
+        _ => NumberFact::from_f64(number.as_f64().powf(power.as_f64()))?,
+    };
+
+    return Some(FactValue::Number(result));
+}
+
+pub fn evaluate_product<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+
+    // Like Excel, the product of no numbers is zero.
+    if numbers.is_empty() {
+        return Some(FactValue::Number(NumberFact::Integer(0)));
+    }
+
+    let mut product = NumberFact::Integer(1);
+    for number in numbers {
+        product = product.checked_mul(&number)?;
+    }
+
+    return Some(FactValue::Number(product));
+}
+
+pub fn evaluate_quotient<'a>(
+    numerator: &ConditionValue,
+    denominator: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numerator = get_number_from_condition_value(numerator, facts, array_context, environment)?;
+    let denominator =
+        get_number_from_condition_value(denominator, facts, array_context, environment)?;
+
+    let quotient = match (numerator, denominator) {
+        (NumberFact::Integer(numerator), NumberFact::Integer(denominator)) => {
+            numerator.checked_div(denominator)?
+        }
+        _ => {
+            if denominator.as_f64() == 0.0 {
+                return None;
+            }
+            float_to_integer((numerator.as_f64() / denominator.as_f64()).trunc())?
+        }
+    };
+
+    return Some(FactValue::Number(NumberFact::Integer(quotient)));
+}
+
+pub fn evaluate_rand(environment: &Environment) -> Option<FactValue> {
+    // The top 53 bits fill the mantissa of a float in [0, 1).
+    let number = (environment.random.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    return Some(FactValue::Number(NumberFact::Float(number)));
+}
+
+pub fn evaluate_rand_between<'a>(
+    bottom: &ConditionValue,
+    top: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let bottom = get_integer_from_condition_value(bottom, facts, array_context, environment)?;
+    let top = get_integer_from_condition_value(top, facts, array_context, environment)?;
+    if bottom > top {
+        return None;
+    }
+
+    // Scale the draw to the range with a widening multiply rather than a modulo, which would
+    // favour the low end of large ranges.
+    let range = (i128::from(top) - i128::from(bottom) + 1) as u128;
+    let offset = (u128::from(environment.random.next_u64()) * range) >> 64;
+    let number = i64::try_from(i128::from(bottom) + offset as i128).ok()?;
+
+    return Some(FactValue::Number(NumberFact::Integer(number)));
+}
+
+/// Apply a function of a real number. Results outside the real numbers, such as the logarithm
+/// of a negative number or the cotangent of zero, have no value.
+pub fn evaluate_real_function<'a>(
+    number: &ConditionValue,
+    function: impl FnOnce(f64) -> f64,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+
+    let result = function(number.as_f64());
+    return Some(FactValue::Number(NumberFact::from_f64(result)?));
+}
+
+pub fn evaluate_round<'a>(
+    number: &ConditionValue,
+    num_digits: Option<&ConditionValue>,
+    rounding: Rounding,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+    let num_digits = match num_digits {
+        Some(value) => get_integer_from_condition_value(value, facts, array_context, environment)?,
+        None => 0,
+    };
+
+    let rounded = round_to_digits(number, num_digits, rounding)?;
+    return Some(FactValue::Number(rounded));
+}
+
+pub fn evaluate_sign<'a>(
+    number: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+
+    let sign = match number {
+        NumberFact::Integer(value) => value.signum(),
+        NumberFact::Float(value) => {
+            if value == 0.0 {
+                0
+            } else {
+                value.signum() as i64
+            }
+        }
+    };
+    return Some(FactValue::Number(NumberFact::Integer(sign)));
+}
+
+pub fn evaluate_sqrt<'a>(
+    number: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+
+    if number.as_f64() < 0.0 {
+        return None;
+    }
+    return Some(FactValue::Number(NumberFact::from_f64(
+        number.as_f64().sqrt(),
+    )?));
+}
+
+pub fn evaluate_subtract<'a>(
+    a: &ConditionValue,
+    b: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let a = get_number_from_condition_value(a, facts, array_context, environment)?;
+    let b = get_number_from_condition_value(b, facts, array_context, environment)?;
+
+    return Some(FactValue::Number(a.checked_sub(&b)?));
+}
+
+pub fn evaluate_sum<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+
+    let mut sum = NumberFact::Integer(0);
+    for number in numbers {
+        sum = sum.checked_add(&number)?;
+    }
+
+    return Some(FactValue::Number(sum));
+}
+
+/// Convert an integral float to an integer, or `None` if it is out of range.
+fn float_to_integer(value: f64) -> Option<i64> {
+    if !value.is_finite() || value < i64::MIN as f64 || value >= i64::MAX as f64 {
+        return None;
+    }
+    return Some(value as i64);
+}
+
+fn round_to_digits(number: NumberFact, num_digits: i64, rounding: Rounding) -> Option<NumberFact> {
+    match number {
+        NumberFact::Integer(value) => {
+            if num_digits >= 0 {
+                return Some(number);
+            }
+
+            let factor = match 10_i64.checked_pow(u32::try_from(-num_digits).ok()?) {
+                Some(factor) => factor,
+                // Every integer is closer to zero than the factor.
+                None => match rounding {
+                    Rounding::Up if value != 0 => return None,
+                    _ => return Some(NumberFact::Integer(0)),
+                },
+            };
+
+            let quotient = value / factor;
+            let remainder = value % factor;
+            let round_away = match rounding {
+                Rounding::Nearest => remainder.abs() >= factor - remainder.abs(),
+                Rounding::Down => false,
+                Rounding::Up => remainder != 0,
+            };
+
+            let quotient = if round_away {
+                quotient + value.signum()
+            } else {
+                quotient
+            };
+            Some(NumberFact::Integer(quotient.checked_mul(factor)?))
+        }
+        NumberFact::Float(value) => {
+            if value == 0.0 || !value.is_finite() {
+                return Some(number);
+            }
+
+            // An f64 has at most 17 significant decimal digits, so rounding past the last of
+            // them leaves the value as it is. Scaling it anyway could overflow to infinity.
+            let exponent = value.abs().log10().floor() as i64;
+            if num_digits >= 16 - exponent {
+                return Some(number);
+            }
+
+            let factor = 10_f64.powi(num_digits.clamp(-308, 308) as i32);
+            if !factor.is_finite() {
+                return Some(number);
+            }
+            let scaled = value * factor;
+            let rounded = match rounding {
+                Rounding::Nearest => scaled.round(),
+                Rounding::Down => scaled.trunc(),
+                Rounding::Up if scaled < 0.0 => scaled.floor(),
+                Rounding::Up => scaled.ceil(),
+            };
+            NumberFact::from_f64(rounded / factor)
+        }
+    }
+}
+
+fn round_to_multiple(
+    number: NumberFact,
+    significance: NumberFact,
+    round_up: bool,
+) -> Option<NumberFact> {
+    if let (NumberFact::Integer(value), NumberFact::Integer(multiple_of)) = (number, significance) {
+        return round_integer_to_multiple(value, multiple_of, round_up);
+    }
+
+    let value = number.as_f64();
+    let multiple_of = significance.as_f64();
+
+    if multiple_of == 0.0 {
+        // Excel returns zero for a ceiling of zero but can't divide for a floor.
+        if round_up {
+            return Some(NumberFact::Integer(0));
+        }
+        return None;
+    }
+    if value > 0.0 && multiple_of < 0.0 {
+        return None;
+    }
+
+    let quotient = value / multiple_of;
+    let multiples = if round_up {
+        quotient.ceil()
+    } else {
+        quotient.floor()
+    };
+    return NumberFact::from_f64(multiples * multiple_of);
+}
+
+/// `round_to_multiple` in integer arithmetic, so integers beyond 2^53 stay exact.
+fn round_integer_to_multiple(value: i64, multiple_of: i64, round_up: bool) -> Option<NumberFact> {
+    if multiple_of == 0 {
+        if round_up {
+            return Some(NumberFact::Integer(0));
+        }
+        return None;
+    }
+    if value > 0 && multiple_of < 0 {
+        return None;
+    }
+
+    let mut multiples = value.checked_div(multiple_of)?;
+    let remainder = value % multiple_of;
+    // The quotient is truncated towards zero, so it is already the floor of a positive quotient
+    // and the ceiling of a negative one.
+    let is_positive = (remainder < 0) == (multiple_of < 0);
+    if remainder != 0 && round_up && is_positive {
+        multiples += 1;
+    }
+    if remainder != 0 && !round_up && !is_positive {
+        multiples -= 1;
+    }
+
+    return Some(NumberFact::Integer(multiples.checked_mul(multiple_of)?));
+}
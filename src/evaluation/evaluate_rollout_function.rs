@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_function::get_number_from_condition_value;
+use super::evaluate_text_function::get_text_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{FactValue, GetFact, NumberFact};
+
+const BUCKETS: u64 = 100;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub fn evaluate_bucket<'a>(
+    value: &ConditionValue,
+    salt: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let bucket = get_bucket(value, salt, facts, array_context, environment)?;
+    return Some(FactValue::Number(NumberFact::Integer(bucket as i64)));
+}
+
+pub fn evaluate_rollout<'a>(
+    value: &ConditionValue,
+    salt: &ConditionValue,
+    percentage: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let bucket = get_bucket(value, salt, facts, array_context, environment)?;
+    let percentage =
+        get_number_from_condition_value(percentage, facts, array_context, environment)?;
+
+    return Some(FactValue::Boolean((bucket as f64) < percentage.as_f64()));
+}
+
+fn get_bucket<'a>(
+    value: &ConditionValue,
+    salt: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<u64> {
+    let value = get_text_from_condition_value(value, facts, array_context, environment)?;
+    let salt = get_text_from_condition_value(salt, facts, array_context, environment)?;
+
+    let key = format!("{}:{}", salt, value);
+    let hash = fmix64(fnv1a(key.as_bytes()));
+
+    // The high bits pick the bucket, so every bucket covers an equal share of hashes.
+    return Some(((u128::from(hash) * u128::from(BUCKETS)) >> 64) as u64);
+}
+
+/// 64-bit FNV-1a. Unlike the standard library hashers it is specified, so buckets do not change
+/// between platforms, builds or Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    return hash;
+}
+
+/// The MurmurHash3 finalizer. FNV-1a barely mixes the last bytes it reads, so without this the
+/// buckets of one value under different salts are correlated.
+fn fmix64(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^= hash >> 33;
+    return hash;
+}
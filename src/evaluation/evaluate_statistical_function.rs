@@ -0,0 +1,554 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::f64::consts::{PI, SQRT_2};
+
+use super::environment::Environment;
+use super::evaluate_function::{
+    get_boolean_from_condition_value, get_integer_from_condition_value,
+    get_number_from_condition_value,
+};
+use super::evaluate_math_function::get_numbers_from_condition_values;
+use super::{get_facts_from_condition_values, get_optional_facts_from_condition_values};
+use crate::conditions::{ConditionValue, ReferenceValue};
+use crate::facts::{FactValue, GetFact, NumberFact};
+
+/// The most trials `BinomDist` accepts. The cumulative probability sums one term per success
+/// count, so this bounds the work a single evaluation can take.
+const MAX_BINOMIAL_TRIALS: i64 = 1_000_000;
+
+pub fn evaluate_average<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+
+    let average = mean(&numbers)?;
+    return Some(FactValue::Number(NumberFact::from_f64(average)?));
+}
+
+pub fn evaluate_count<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let count = get_facts_from_condition_values(values, facts, array_context, environment)
+        .iter()
+        .filter(|fact| matches!(fact, FactValue::Number(_)))
+        .count();
+
+    return Some(FactValue::Number(NumberFact::Integer(count as i64)));
+}
+
+pub fn evaluate_count_a<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let count = get_facts_from_condition_values(values, facts, array_context, environment).len();
+
+    return Some(FactValue::Number(NumberFact::Integer(count as i64)));
+}
+
+pub fn evaluate_binom_dist<'a>(
+    successes: &ConditionValue,
+    trials: &ConditionValue,
+    probability: &ConditionValue,
+    cumulative: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let successes = get_integer_from_condition_value(successes, facts, array_context, environment)?;
+    let trials = get_integer_from_condition_value(trials, facts, array_context, environment)?;
+    let probability =
+        get_number_from_condition_value(probability, facts, array_context, environment)?.as_f64();
+    let cumulative =
+        get_boolean_from_condition_value(cumulative, facts, array_context, environment)?;
+
+    if successes < 0
+        || successes > trials
+        || trials > MAX_BINOMIAL_TRIALS
+        || !(0.0..=1.0).contains(&probability)
+    {
+        return None;
+    }
+
+    let binomial_probability = |k: i64| binomial_probability(k, trials, probability);
+    let result = if !cumulative {
+        binomial_probability(successes)
+    } else if successes < trials / 2 {
+        (0..=successes).map(binomial_probability).sum()
+    } else {
+        // Sum the shorter tail.
+        1.0 - ((successes + 1)..=trials)
+            .map(binomial_probability)
+            .sum::<f64>()
+    };
+
+    return Some(FactValue::Number(NumberFact::from_f64(
+        result.clamp(0.0, 1.0),
+    )?));
+}
+
+pub fn evaluate_forecast<'a>(
+    x: &ConditionValue,
+    known_ys: &Vec<ConditionValue>,
+    known_xs: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let x = get_number_from_condition_value(x, facts, array_context, environment)?.as_f64();
+    let (slope, intercept) =
+        least_squares_line(known_ys, known_xs, facts, array_context, environment)?;
+
+    let forecast = intercept + slope * x;
+    return Some(FactValue::Number(NumberFact::from_f64(forecast)?));
+}
+
+pub fn evaluate_intercept<'a>(
+    known_ys: &Vec<ConditionValue>,
+    known_xs: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let (_, intercept) = least_squares_line(known_ys, known_xs, facts, array_context, environment)?;
+    return Some(FactValue::Number(NumberFact::from_f64(intercept)?));
+}
+
+pub fn evaluate_median<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = sorted(get_numbers_from_condition_values(
+        values,
+        facts,
+        array_context,
+        environment,
+    )?);
+    if numbers.is_empty() {
+        return None;
+    }
+
+    let middle = numbers.len() / 2;
+    if numbers.len() % 2 == 1 {
+        return Some(FactValue::Number(numbers[middle]));
+    }
+
+    let median = (numbers[middle - 1].as_f64() + numbers[middle].as_f64()) / 2.0;
+    return Some(FactValue::Number(NumberFact::from_f64(median)?));
+}
+
+pub fn evaluate_mode<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+
+    let mut mode: Option<NumberFact> = None;
+    let mut mode_count = 1;
+    for number in &numbers {
+        let count = numbers.iter().filter(|other| *other == number).count();
+        if count > mode_count {
+            mode = Some(*number);
+            mode_count = count;
+        }
+    }
+
+    // Like Excel, there is no mode when no number repeats.
+    return Some(FactValue::Number(mode?));
+}
+
+pub fn evaluate_norm_dist<'a>(
+    x: &ConditionValue,
+    mean: &ConditionValue,
+    standard_dev: &ConditionValue,
+    cumulative: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let x = get_number_from_condition_value(x, facts, array_context, environment)?.as_f64();
+    let mean = get_number_from_condition_value(mean, facts, array_context, environment)?.as_f64();
+    let standard_dev =
+        get_number_from_condition_value(standard_dev, facts, array_context, environment)?.as_f64();
+    let cumulative =
+        get_boolean_from_condition_value(cumulative, facts, array_context, environment)?;
+
+    if standard_dev <= 0.0 {
+        return None;
+    }
+
+    let z = (x - mean) / standard_dev;
+    let result = if cumulative {
+        standard_normal_cdf(z)
+    } else {
+        standard_normal_pdf(z) / standard_dev
+    };
+    return Some(FactValue::Number(NumberFact::from_f64(result)?));
+}
+
+pub fn evaluate_norm_inv<'a>(
+    probability: &ConditionValue,
+    mean: &ConditionValue,
+    standard_dev: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let probability =
+        get_number_from_condition_value(probability, facts, array_context, environment)?.as_f64();
+    let mean = get_number_from_condition_value(mean, facts, array_context, environment)?.as_f64();
+    let standard_dev =
+        get_number_from_condition_value(standard_dev, facts, array_context, environment)?.as_f64();
+
+    if probability <= 0.0 || probability >= 1.0 || standard_dev <= 0.0 {
+        return None;
+    }
+
+    let x = mean + standard_dev * inverse_standard_normal_cdf(probability);
+    return Some(FactValue::Number(NumberFact::from_f64(x)?));
+}
+
+pub fn evaluate_norm_s_dist<'a>(
+    z: &ConditionValue,
+    cumulative: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let z = get_number_from_condition_value(z, facts, array_context, environment)?.as_f64();
+    let cumulative =
+        get_boolean_from_condition_value(cumulative, facts, array_context, environment)?;
+
+    let result = if cumulative {
+        standard_normal_cdf(z)
+    } else {
+        standard_normal_pdf(z)
+    };
+    return Some(FactValue::Number(NumberFact::from_f64(result)?));
+}
+
+/// The k-th largest number, or the k-th smallest when `largest` is false.
+pub fn evaluate_nth<'a>(
+    values: &Vec<ConditionValue>,
+    k: &ConditionValue,
+    largest: bool,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let mut numbers = sorted(get_numbers_from_condition_values(
+        values,
+        facts,
+        array_context,
+        environment,
+    )?);
+    let k = get_integer_from_condition_value(k, facts, array_context, environment)?;
+
+    if largest {
+        numbers.reverse();
+    }
+    let index = usize::try_from(k).ok()?.checked_sub(1)?;
+    return Some(FactValue::Number(*numbers.get(index)?));
+}
+
+pub fn evaluate_percentile<'a>(
+    values: &Vec<ConditionValue>,
+    k: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = sorted(get_numbers_from_condition_values(
+        values,
+        facts,
+        array_context,
+        environment,
+    )?);
+    let k = get_number_from_condition_value(k, facts, array_context, environment)?.as_f64();
+
+    if numbers.is_empty() || !(0.0..=1.0).contains(&k) {
+        return None;
+    }
+
+    let rank = k * (numbers.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let fraction = rank - lower as f64;
+    if fraction == 0.0 {
+        return Some(FactValue::Number(numbers[lower]));
+    }
+
+    let lower_value = numbers[lower].as_f64();
+    let upper_value = numbers[lower + 1].as_f64();
+    let percentile = lower_value + fraction * (upper_value - lower_value);
+    return Some(FactValue::Number(NumberFact::from_f64(percentile)?));
+}
+
+pub fn evaluate_rank<'a>(
+    number: &ConditionValue,
+    values: &Vec<ConditionValue>,
+    order: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let number = get_number_from_condition_value(number, facts, array_context, environment)?;
+    let numbers = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+    let ascending = match order {
+        Some(order) => {
+            get_integer_from_condition_value(order, facts, array_context, environment)? != 0
+        }
+        None => false,
+    };
+
+    if !numbers.contains(&number) {
+        return None;
+    }
+    let ranked_before = numbers
+        .iter()
+        .filter(|other| {
+            if ascending {
+                **other < number
+            } else {
+                **other > number
+            }
+        })
+        .count();
+
+    return Some(FactValue::Number(NumberFact::Integer(
+        ranked_before as i64 + 1,
+    )));
+}
+
+pub fn evaluate_rows<'a>(
+    array: &ReferenceValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+) -> Option<FactValue> {
+    let rows = facts.get_array_len(&array.address, array_context);
+    return Some(FactValue::Number(NumberFact::Integer(rows)));
+}
+
+pub fn evaluate_slope<'a>(
+    known_ys: &Vec<ConditionValue>,
+    known_xs: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let (slope, _) = least_squares_line(known_ys, known_xs, facts, array_context, environment)?;
+    return Some(FactValue::Number(NumberFact::from_f64(slope)?));
+}
+
+pub fn evaluate_standardize<'a>(
+    x: &ConditionValue,
+    mean: &ConditionValue,
+    standard_dev: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let x = get_number_from_condition_value(x, facts, array_context, environment)?.as_f64();
+    let mean = get_number_from_condition_value(mean, facts, array_context, environment)?.as_f64();
+    let standard_dev =
+        get_number_from_condition_value(standard_dev, facts, array_context, environment)?.as_f64();
+
+    if standard_dev <= 0.0 {
+        return None;
+    }
+
+    let z = (x - mean) / standard_dev;
+    return Some(FactValue::Number(NumberFact::from_f64(z)?));
+}
+
+pub fn evaluate_stdev<'a>(
+    values: &Vec<ConditionValue>,
+    sample: bool,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+
+    let stdev = variance(&numbers, sample)?.sqrt();
+    return Some(FactValue::Number(NumberFact::from_f64(stdev)?));
+}
+
+pub fn evaluate_var<'a>(
+    values: &Vec<ConditionValue>,
+    sample: bool,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let numbers = get_numbers_from_condition_values(values, facts, array_context, environment)?;
+
+    let variance = variance(&numbers, sample)?;
+    return Some(FactValue::Number(NumberFact::from_f64(variance)?));
+}
+
+pub(super) fn sorted(mut numbers: Vec<NumberFact>) -> Vec<NumberFact> {
+    numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    return numbers;
+}
+
+pub(super) fn mean(numbers: &[NumberFact]) -> Option<f64> {
+    if numbers.is_empty() {
+        return None;
+    }
+
+    let sum: f64 = numbers.iter().map(|number| number.as_f64()).sum();
+    return Some(sum / numbers.len() as f64);
+}
+
+/// Sample variance divides by n - 1 and needs at least two numbers; population variance
+/// divides by n.
+pub(super) fn variance(numbers: &[NumberFact], sample: bool) -> Option<f64> {
+    let mean = mean(numbers)?;
+    let degrees_of_freedom = if sample {
+        numbers.len() - 1
+    } else {
+        numbers.len()
+    };
+    if degrees_of_freedom == 0 {
+        return None;
+    }
+
+    let sum_of_squares: f64 = numbers
+        .iter()
+        .map(|number| (number.as_f64() - mean).powi(2))
+        .sum();
+    return Some(sum_of_squares / degrees_of_freedom as f64);
+}
+
+/// The slope and intercept of the least squares line through the known points. A point is
+/// left out when its y or its x is missing, as in Excel. There is no line when the lists differ
+/// in length or every x is the same.
+fn least_squares_line<'a>(
+    known_ys: &Vec<ConditionValue>,
+    known_xs: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<(f64, f64)> {
+    let ys = get_optional_facts_from_condition_values(known_ys, facts, array_context, environment);
+    let xs = get_optional_facts_from_condition_values(known_xs, facts, array_context, environment);
+    if ys.len() != xs.len() {
+        return None;
+    }
+
+    let mut points = Vec::with_capacity(ys.len());
+    for pair in ys.into_iter().zip(xs) {
+        match pair {
+            (Some(FactValue::Number(y)), Some(FactValue::Number(x))) => points.push((y, x)),
+            (None, _) | (_, None) => {}
+            _ => return None,
+        }
+    }
+    let (ys, xs): (Vec<NumberFact>, Vec<NumberFact>) = points.into_iter().unzip();
+
+    let mean_y = mean(&ys)?;
+    let mean_x = mean(&xs)?;
+
+    let mut covariance = 0.0;
+    let mut x_variance = 0.0;
+    for (y, x) in ys.iter().zip(&xs) {
+        let x_deviation = x.as_f64() - mean_x;
+        covariance += x_deviation * (y.as_f64() - mean_y);
+        x_variance += x_deviation * x_deviation;
+    }
+    if x_variance == 0.0 {
+        return None;
+    }
+
+    let slope = covariance / x_variance;
+    return Some((slope, mean_y - slope * mean_x));
+}
+
+fn standard_normal_pdf(z: f64) -> f64 {
+    return (-z * z / 2.0).exp() / (2.0 * PI).sqrt();
+}
+
+fn standard_normal_cdf(z: f64) -> f64 {
+    return 0.5 * libm::erfc(-z / SQRT_2);
+}
+
+/// The standard normal quantile, using Acklam's rational approximation refined with a step
+/// of Halley's method, which is accurate to about 15 digits.
+fn inverse_standard_normal_cdf(probability: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const LOW_TAIL: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    let x = if probability < LOW_TAIL {
+        tail((-2.0 * probability.ln()).sqrt())
+    } else if probability > 1.0 - LOW_TAIL {
+        -tail((-2.0 * (1.0 - probability).ln()).sqrt())
+    } else {
+        let q = probability - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+
+    let error = standard_normal_cdf(x) - probability;
+    let u = error * (2.0 * PI).sqrt() * (x * x / 2.0).exp();
+    return x - u / (1.0 + x * u / 2.0);
+}
+
+/// The probability of exactly `successes` in `trials`, computed through logarithms so that
+/// large numbers of trials don't overflow.
+fn binomial_probability(successes: i64, trials: i64, probability: f64) -> f64 {
+    if probability == 0.0 {
+        return if successes == 0 { 1.0 } else { 0.0 };
+    }
+    if probability == 1.0 {
+        return if successes == trials { 1.0 } else { 0.0 };
+    }
+
+    let (n, k) = (trials as f64, successes as f64);
+    let ln_combinations = libm::lgamma(n + 1.0) - libm::lgamma(k + 1.0) - libm::lgamma(n - k + 1.0);
+    return (ln_combinations + k * probability.ln() + (n - k) * (1.0 - probability).ln()).exp();
+}
@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::evaluate_function::{
+    get_boolean_from_condition_value, get_integer_from_condition_value,
+};
+use super::{get_fact_from_condition_value, get_facts_from_condition_values};
+use crate::conditions::{functions::Pattern, ConditionValue};
+use crate::facts::{DateFact, FactValue, GetFact, NumberFact};
+
+/// Resolve a value as text. Numbers, booleans and dates are converted as Excel would.
+pub(super) fn get_text_from_condition_value<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<String> {
+    let fact = get_fact_from_condition_value(value, facts, array_context, environment)?;
+    return Some(fact_to_text(&fact));
+}
+
+pub(super) fn fact_to_text(fact: &FactValue) -> String {
+    match fact {
+        FactValue::String(text) => text.to_string(),
+        FactValue::Number(NumberFact::Integer(number)) => number.to_string(),
+        FactValue::Number(NumberFact::Float(number)) => number.to_string(),
+        FactValue::Boolean(true) => "TRUE".to_string(),
+        FactValue::Boolean(false) => "FALSE".to_string(),
+        FactValue::Date(DateFact::Date(date)) => date.to_string(),
+        FactValue::Date(DateFact::DateTime(date_time)) => {
+            date_time.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+        }
+        FactValue::Date(DateFact::Timestamp(timestamp)) => timestamp.to_rfc3339(),
+        FactValue::Array(items) => items.iter().map(fact_to_text).collect::<Vec<_>>().join(","),
+    }
+}
+
+/// Resolve an optional character count, defaulting to one as Excel does.
+fn get_num_chars<'a>(
+    num_chars: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<usize> {
+    let num_chars = match num_chars {
+        Some(value) => get_integer_from_condition_value(value, facts, array_context, environment)?,
+        None => 1,
+    };
+    return usize::try_from(num_chars).ok();
+}
+
+pub fn evaluate_concat<'a>(
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let mut concatenated = String::new();
+
+    for value in values {
+        let text = get_text_from_condition_value(value, facts, array_context, environment)?;
+        concatenated.push_str(&text);
+    }
+
+    return Some(FactValue::String(concatenated));
+}
+
+pub fn evaluate_exact<'a>(
+    a: &ConditionValue,
+    b: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let a = get_text_from_condition_value(a, facts, array_context, environment)?;
+    let b = get_text_from_condition_value(b, facts, array_context, environment)?;
+
+    return Some(FactValue::Boolean(a == b));
+}
+
+pub fn evaluate_extract<'a>(
+    text: &ConditionValue,
+    pattern: &Pattern,
+    group: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let group = match group {
+        Some(group) => get_fact_from_condition_value(group, facts, array_context, environment)?,
+        None => FactValue::Number(NumberFact::Integer(0)),
+    };
+
+    let captures = pattern.regex()?.captures(&text)?;
+    let capture = match group {
+        FactValue::Number(NumberFact::Integer(index)) => captures.get(usize::try_from(index).ok()?),
+        FactValue::String(name) => captures.name(&name),
+        _ => None,
+    }?;
+
+    return Some(FactValue::String(capture.as_str().to_string()));
+}
+
+pub fn evaluate_find<'a>(
+    find: &ConditionValue,
+    within: &ConditionValue,
+    start_num: &Option<Box<ConditionValue>>,
+    case_sensitive: bool,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let mut find = get_text_from_condition_value(find, facts, array_context, environment)?;
+    let mut within = get_text_from_condition_value(within, facts, array_context, environment)?;
+    let start_num = match start_num {
+        Some(value) => get_integer_from_condition_value(value, facts, array_context, environment)?,
+        None => 1,
+    };
+
+    if !case_sensitive {
+        find = find.to_lowercase();
+        within = within.to_lowercase();
+    }
+
+    let within: Vec<char> = within.chars().collect();
+    let find: Vec<char> = find.chars().collect();
+    if start_num < 1 || start_num as usize > within.len() + 1 {
+        return None;
+    }
+
+    let start_index = start_num as usize - 1;
+    if find.is_empty() {
+        return Some(FactValue::Number(NumberFact::Integer(start_num)));
+    }
+
+    let position = within[start_index..]
+        .windows(find.len())
+        .position(|window| window == find.as_slice())?;
+
+    let found_at = (start_index + position + 1) as i64;
+    return Some(FactValue::Number(NumberFact::Integer(found_at)));
+}
+
+pub fn evaluate_left<'a>(
+    text: &ConditionValue,
+    num_chars: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let num_chars = get_num_chars(num_chars, facts, array_context, environment)?;
+
+    return Some(FactValue::String(text.chars().take(num_chars).collect()));
+}
+
+pub fn evaluate_len<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+
+    let len = text.chars().count() as i64;
+    return Some(FactValue::Number(NumberFact::Integer(len)));
+}
+
+pub fn evaluate_lower<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    return Some(FactValue::String(text.to_lowercase()));
+}
+
+pub fn evaluate_matches<'a>(
+    text: &ConditionValue,
+    pattern: &Pattern,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+
+    return Some(FactValue::Boolean(pattern.regex()?.is_match(&text)));
+}
+
+pub fn evaluate_mid<'a>(
+    text: &ConditionValue,
+    start: &ConditionValue,
+    num_chars: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let start = get_integer_from_condition_value(start, facts, array_context, environment)?;
+    let num_chars = get_integer_from_condition_value(num_chars, facts, array_context, environment)?;
+
+    if start < 1 || num_chars < 0 {
+        return None;
+    }
+
+    let mid = text
+        .chars()
+        .skip(start as usize - 1)
+        .take(num_chars as usize)
+        .collect();
+    return Some(FactValue::String(mid));
+}
+
+pub fn evaluate_proper<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+
+    let mut proper = String::with_capacity(text.len());
+    let mut previous_is_letter = false;
+    for character in text.chars() {
+        if previous_is_letter {
+            proper.extend(character.to_lowercase());
+        } else {
+            proper.extend(character.to_uppercase());
+        }
+        previous_is_letter = character.is_alphabetic();
+    }
+
+    return Some(FactValue::String(proper));
+}
+
+pub fn evaluate_replace<'a>(
+    old_text: &ConditionValue,
+    start: &ConditionValue,
+    num_chars: &ConditionValue,
+    new_text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let old_text = get_text_from_condition_value(old_text, facts, array_context, environment)?;
+    let start = get_integer_from_condition_value(start, facts, array_context, environment)?;
+    let num_chars = get_integer_from_condition_value(num_chars, facts, array_context, environment)?;
+    let new_text = get_text_from_condition_value(new_text, facts, array_context, environment)?;
+
+    if start < 1 || num_chars < 0 {
+        return None;
+    }
+
+    let start_index = start as usize - 1;
+    let mut replaced: String = old_text.chars().take(start_index).collect();
+    replaced.push_str(&new_text);
+    replaced.extend(
+        old_text
+            .chars()
+            .skip(start_index.saturating_add(num_chars as usize)),
+    );
+
+    return Some(FactValue::String(replaced));
+}
+
+pub fn evaluate_rept<'a>(
+    text: &ConditionValue,
+    num: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let num = get_integer_from_condition_value(num, facts, array_context, environment)?;
+
+    // Excel caps text at 32,767 characters; do the same rather than allocate without bound.
+    let num = usize::try_from(num).ok()?;
+    if text.chars().count().checked_mul(num)? > 32_767 {
+        return None;
+    }
+
+    return Some(FactValue::String(text.repeat(num)));
+}
+
+pub fn evaluate_right<'a>(
+    text: &ConditionValue,
+    num_chars: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let num_chars = get_num_chars(num_chars, facts, array_context, environment)?;
+
+    let len = text.chars().count();
+    let right = text.chars().skip(len.saturating_sub(num_chars)).collect();
+    return Some(FactValue::String(right));
+}
+
+pub fn evaluate_split<'a>(
+    text: &ConditionValue,
+    delimiter: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let delimiter = get_text_from_condition_value(delimiter, facts, array_context, environment)?;
+    if delimiter.is_empty() {
+        return None;
+    }
+    if text.trim().is_empty() {
+        return Some(FactValue::Array(Vec::new()));
+    }
+
+    let items = text
+        .split(&delimiter)
+        .map(|item| FactValue::String(item.trim().to_string()))
+        .collect();
+    return Some(FactValue::Array(items));
+}
+
+pub fn evaluate_substitute<'a>(
+    text: &ConditionValue,
+    old: &ConditionValue,
+    new: &ConditionValue,
+    instance: &Option<Box<ConditionValue>>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    let old = get_text_from_condition_value(old, facts, array_context, environment)?;
+    let new = get_text_from_condition_value(new, facts, array_context, environment)?;
+
+    if old.is_empty() {
+        return Some(FactValue::String(text));
+    }
+
+    let instance = match instance {
+        Some(value) => get_integer_from_condition_value(value, facts, array_context, environment)?,
+        None => return Some(FactValue::String(text.replace(&old, &new))),
+    };
+    if instance < 1 {
+        return None;
+    }
+
+    match text.match_indices(&old).nth(instance as usize - 1) {
+        Some((index, _)) => {
+            let mut substituted = text[..index].to_string();
+            substituted.push_str(&new);
+            substituted.push_str(&text[index + old.len()..]);
+            Some(FactValue::String(substituted))
+        }
+        None => Some(FactValue::String(text)),
+    }
+}
+
+pub fn evaluate_text_join<'a>(
+    delimiter: &ConditionValue,
+    ignore_empty: &ConditionValue,
+    values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let delimiter = get_text_from_condition_value(delimiter, facts, array_context, environment)?;
+    let ignore_empty =
+        get_boolean_from_condition_value(ignore_empty, facts, array_context, environment)?;
+
+    let texts: Vec<String> =
+        get_facts_from_condition_values(values, facts, array_context, environment)
+            .iter()
+            .map(fact_to_text)
+            .filter(|text| !(ignore_empty && text.is_empty()))
+            .collect();
+
+    return Some(FactValue::String(texts.join(&delimiter)));
+}
+
+pub fn evaluate_trim<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+
+    let trimmed = text
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    return Some(FactValue::String(trimmed));
+}
+
+pub fn evaluate_upper<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+    return Some(FactValue::String(text.to_uppercase()));
+}
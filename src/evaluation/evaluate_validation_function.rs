@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use super::environment::Environment;
+use super::get_fact_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{FactValue, GetFact};
+
+const MAX_EMAIL_LENGTH: usize = 254;
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+const MAX_LABEL_LENGTH: usize = 63;
+
+const MAX_PHONE_NUMBER_DIGITS: usize = 15;
+
+const MIN_IBAN_LENGTH: usize = 15;
+const MAX_IBAN_LENGTH: usize = 34;
+
+pub fn evaluate_is_email<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    return validate(value, is_email, facts, array_context, environment);
+}
+
+pub fn evaluate_is_iban<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    return validate(value, is_iban, facts, array_context, environment);
+}
+
+pub fn evaluate_is_luhn<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    return validate(value, is_luhn, facts, array_context, environment);
+}
+
+pub fn evaluate_is_phone_number<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    return validate(value, is_phone_number, facts, array_context, environment);
+}
+
+pub fn evaluate_is_uuid<'a>(
+    value: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    return validate(value, is_uuid, facts, array_context, environment);
+}
+
+fn validate<'a>(
+    value: &ConditionValue,
+    is_valid: fn(&str) -> bool,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let is_valid = match get_fact_from_condition_value(value, facts, array_context, environment) {
+        Some(FactValue::String(text)) => is_valid(&text),
+        _ => false,
+    };
+    return Some(FactValue::Boolean(is_valid));
+}
+
+fn is_email(text: &str) -> bool {
+    let Some((local_part, domain)) = text.rsplit_once('@') else {
+        return false;
+    };
+    if text.len() > MAX_EMAIL_LENGTH || local_part.len() > MAX_LOCAL_PART_LENGTH {
+        return false;
+    }
+
+    let is_local_part = local_part.split('.').all(|atom| {
+        !atom.is_empty()
+            && atom
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-".contains(c))
+    });
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let is_domain = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= MAX_LABEL_LENGTH
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+        && !labels[labels.len() - 1].chars().all(|c| c.is_ascii_digit());
+
+    return is_local_part && is_domain;
+}
+
+fn is_phone_number(text: &str) -> bool {
+    let Some(digits) = text.strip_prefix('+') else {
+        return false;
+    };
+    return !digits.is_empty()
+        && digits.len() <= MAX_PHONE_NUMBER_DIGITS
+        && !digits.starts_with('0')
+        && digits.chars().all(|c| c.is_ascii_digit());
+}
+
+/// Check the shape and check digits of an IBAN with ISO 13616: the country code and check digits
+/// move to the end, letters become the numbers 10 to 35, and the result must be 1 modulo 97. The
+/// country code is only checked to be two letters, not looked up in the registry.
+fn is_iban(text: &str) -> bool {
+    let iban: Vec<char> = text
+        .chars()
+        .filter(|c| *c != ' ')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if iban.len() < MIN_IBAN_LENGTH
+        || iban.len() > MAX_IBAN_LENGTH
+        || !iban[..2].iter().all(|c| c.is_ascii_uppercase())
+        || !iban[2..4].iter().all(|c| c.is_ascii_digit())
+        || !iban.iter().all(|c| c.is_ascii_alphanumeric())
+    {
+        return false;
+    }
+
+    let mut remainder = 0;
+    for c in iban[4..].iter().chain(&iban[..4]) {
+        let value = c.to_digit(36).unwrap();
+        remainder = if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        };
+    }
+    return remainder == 1;
+}
+
+fn is_luhn(text: &str) -> bool {
+    let mut digits = Vec::new();
+    for c in text.chars() {
+        match c {
+            '0'..='9' => digits.push(c.to_digit(10).unwrap()),
+            ' ' | '-' => {}
+            _ => return false,
+        }
+    }
+    if digits.len() < 2 {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| match (i % 2, digit * 2) {
+            (0, _) => *digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    return sum.is_multiple_of(10);
+}
+
+fn is_uuid(text: &str) -> bool {
+    let groups: Vec<&str> = text.split('-').collect();
+    return groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, length)| {
+            group.len() == length && group.chars().all(|c| c.is_ascii_hexdigit())
+        });
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use url::Url;
+
+use super::environment::Environment;
+use super::evaluate_text_function::get_text_from_condition_value;
+use crate::conditions::ConditionValue;
+use crate::facts::{FactValue, GetFact};
+
+pub fn evaluate_encode_url<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    return Some(FactValue::String(encoded));
+}
+
+pub fn evaluate_decode_url<'a>(
+    text: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let text = get_text_from_condition_value(text, facts, array_context, environment)?;
+
+    let mut bytes = Vec::new();
+    let mut remaining = text.as_bytes();
+    while let Some((&byte, rest)) = remaining.split_first() {
+        if byte == b'%' {
+            // from_str_radix would also take a sign, as in "%+F".
+            let hex = rest.get(..2)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            remaining = &rest[2..];
+        } else {
+            bytes.push(byte);
+            remaining = rest;
+        }
+    }
+
+    let decoded = String::from_utf8(bytes).ok()?;
+    return Some(FactValue::String(decoded));
+}
+
+pub fn evaluate_url_scheme<'a>(
+    url: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let url = get_url_from_condition_value(url, facts, array_context, environment)?;
+    return Some(FactValue::String(url.scheme().to_string()));
+}
+
+pub fn evaluate_url_host<'a>(
+    url: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let url = get_url_from_condition_value(url, facts, array_context, environment)?;
+    let host = url.host_str()?;
+    return Some(FactValue::String(host.to_string()));
+}
+
+pub fn evaluate_url_path<'a>(
+    url: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let url = get_url_from_condition_value(url, facts, array_context, environment)?;
+    return Some(FactValue::String(url.path().to_string()));
+}
+
+pub fn evaluate_url_query_parameter<'a>(
+    url: &ConditionValue,
+    name: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<FactValue> {
+    let url = get_url_from_condition_value(url, facts, array_context, environment)?;
+    let name = get_text_from_condition_value(name, facts, array_context, environment)?;
+
+    let (_, value) = url.query_pairs().find(|(key, _)| *key == name)?;
+    return Some(FactValue::String(value.into_owned()));
+}
+
+fn get_url_from_condition_value<'a>(
+    url: &ConditionValue,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Option<Url> {
+    let text = get_text_from_condition_value(url, facts, array_context, environment)?;
+    return Url::parse(text.trim()).ok();
+}
@@ -1,33 +1,76 @@
 use std::collections::HashMap;
 
+pub mod environment;
+mod evaluate_array_function;
+mod evaluate_business_day_function;
+mod evaluate_conditional_aggregate_function;
+mod evaluate_conversion_function;
+mod evaluate_date_function;
+mod evaluate_engineering_function;
+mod evaluate_financial_function;
 mod evaluate_function;
+mod evaluate_information_function;
+mod evaluate_logical_function;
+mod evaluate_lookup_function;
+mod evaluate_math_function;
+mod evaluate_rollout_function;
+mod evaluate_statistical_function;
+mod evaluate_text_function;
+mod evaluate_validation_function;
+mod evaluate_web_function;
 
 use crate::{
     conditions::{
         operators::{AggregationOperator, ComparisonOperator},
         ArrayCondition, Condition, ConditionGroup, ConditionValue, SingleCondition,
+        ValuesCondition,
     },
     facts::{self, FactValue, GetFact},
 };
 
+use self::environment::Environment;
 use self::evaluate_function::evaluate_fn;
 
 pub fn evaluate(condition: &Condition, facts: &impl facts::GetFact) -> bool {
+    return evaluate_with_environment(condition, facts, &Environment::new());
+}
+
+pub fn evaluate_with_environment(
+    condition: &Condition,
+    facts: &impl facts::GetFact,
+    environment: &Environment,
+) -> bool {
     let array_context: HashMap<&str, i64> = HashMap::new();
-    return evaluate_with_array_context(condition, facts, &array_context);
+    return evaluate_condition(condition, facts, &array_context, environment);
 }
 
 pub fn evaluate_with_array_context(
     condition: &Condition,
     facts: &impl facts::GetFact,
     array_context: &HashMap<&str, i64>,
+) -> bool {
+    return evaluate_condition(condition, facts, array_context, &Environment::new());
+}
+
+fn evaluate_condition(
+    condition: &Condition,
+    facts: &impl facts::GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> bool {
     match condition {
-        Condition::Single(condition) => evaluate_single_condition(condition, facts, array_context),
+        Condition::Single(condition) => {
+            evaluate_single_condition(condition, facts, array_context, environment)
+        }
         Condition::Group(condition_group) => {
-            evaluate_condition_group(condition_group, facts, array_context)
+            evaluate_condition_group(condition_group, facts, array_context, environment)
+        }
+        Condition::Array(condition) => {
+            evaluate_array_condition(condition, facts, array_context, environment)
+        }
+        Condition::Values(condition) => {
+            evaluate_values_condition(condition, facts, array_context, environment)
         }
-        Condition::Array(condition) => evaluate_array_condition(condition, facts, array_context),
     }
 }
 
@@ -35,9 +78,10 @@ fn evaluate_single_condition(
     condition: &SingleCondition,
     facts: &impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> bool {
-    let a = get_fact_from_condition_value(&condition.values.0, facts, &array_context);
-    let b = get_fact_from_condition_value(&condition.values.1, facts, &array_context);
+    let a = get_fact_from_condition_value(&condition.values.0, facts, &array_context, environment);
+    let b = get_fact_from_condition_value(&condition.values.1, facts, &array_context, environment);
 
     match condition.operator {
         ComparisonOperator::Equals => a == b,
@@ -45,6 +89,14 @@ fn evaluate_single_condition(
         ComparisonOperator::LessThan => a < b,
         ComparisonOperator::GreaterThanEqualTo => a >= b,
         ComparisonOperator::LessThanEqualTo => a <= b,
+        ComparisonOperator::In => match (a, b) {
+            (Some(a), Some(FactValue::Array(items))) => items.contains(&a),
+            _ => false,
+        },
+        ComparisonOperator::NotIn => match (a, b) {
+            (Some(a), Some(FactValue::Array(items))) => !items.contains(&a),
+            _ => false,
+        },
     }
 }
 
@@ -52,16 +104,17 @@ fn evaluate_condition_group(
     condition_group: &ConditionGroup,
     facts: &impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> bool {
     let mut condition_group_iter = condition_group.conditions.iter();
 
     match condition_group.operator {
         AggregationOperator::Any => condition_group_iter
-            .any(|condition| evaluate_with_array_context(&condition, facts, array_context)),
+            .any(|condition| evaluate_condition(&condition, facts, array_context, environment)),
         AggregationOperator::Every => condition_group_iter
-            .all(|condition| evaluate_with_array_context(&condition, facts, array_context)),
+            .all(|condition| evaluate_condition(&condition, facts, array_context, environment)),
         AggregationOperator::None => condition_group_iter
-            .all(|condition| !evaluate_with_array_context(&condition, facts, array_context)),
+            .all(|condition| !evaluate_condition(&condition, facts, array_context, environment)),
     }
 }
 
@@ -69,50 +122,207 @@ fn evaluate_array_condition<'a>(
     condition: &'a ArrayCondition,
     facts: &impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> bool {
-    let array_len = facts.get_array_len(&condition.array.address, array_context);
+    return evaluate_for_array_items(
+        &condition.operator,
+        &condition.array.address,
+        &condition.condition,
+        facts,
+        array_context,
+        environment,
+    );
+}
 
-    for i in 0..array_len {
-        let mut item_context: HashMap<&str, i64> = HashMap::new();
-        item_context.insert(&condition.array.address, i);
-        item_context.extend(array_context);
+fn evaluate_values_condition(
+    condition: &ValuesCondition,
+    facts: &impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> bool {
+    let items =
+        match get_fact_from_condition_value(&condition.values, facts, array_context, environment) {
+            Some(FactValue::Array(items)) => items,
+            Some(item) => vec![item],
+            None => Vec::new(),
+        };
+    let item_facts = ValueItemFacts {
+        facts,
+        item: &condition.item,
+        items,
+    };
 
-        let item_result = evaluate_with_array_context(&condition.condition, facts, &item_context);
+    return evaluate_for_array_items(
+        &condition.operator,
+        &condition.item,
+        &condition.condition,
+        &item_facts,
+        array_context,
+        environment,
+    );
+}
 
-        match condition.operator {
-            AggregationOperator::Every => {
-                if !item_result {
-                    return false;
-                }
-            }
-            AggregationOperator::Any => {
-                if item_result {
-                    return true;
-                }
-            }
-            AggregationOperator::None => {
-                if item_result {
-                    return false;
-                }
-            }
+/// The facts with the values of a values condition at `item[]`.
+struct ValueItemFacts<'f> {
+    facts: &'f dyn GetFact,
+    item: &'f str,
+    items: Vec<FactValue>,
+}
+
+impl GetFact for ValueItemFacts<'_> {
+    fn get_fact(
+        &self,
+        fact_address: &str,
+        array_context: &HashMap<&str, i64>,
+    ) -> Option<FactValue> {
+        if fact_address.strip_prefix(self.item) != Some("[]") {
+            return self.facts.get_fact(fact_address, array_context);
         }
+
+        let index = usize::try_from(*array_context.get(self.item)?).ok()?;
+        return self.items.get(index).cloned();
     }
 
-    match condition.operator {
-        AggregationOperator::Every => true,
-        AggregationOperator::Any => false,
-        AggregationOperator::None => true,
+    fn get_array_len(&self, address: &str, array_context: &HashMap<&str, i64>) -> i64 {
+        if address == self.item {
+            return self.items.len() as i64;
+        }
+        return self.facts.get_array_len(address, array_context);
     }
 }
 
+/// Evaluate a condition once for each item of an array and aggregate the results.
+fn evaluate_for_array_items(
+    operator: &AggregationOperator,
+    array_address: &str,
+    condition: &Condition,
+    facts: &impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> bool {
+    let mut item_results = get_item_contexts(array_address, facts, array_context)
+        .map(|item_context| evaluate_condition(condition, facts, &item_context, environment));
+
+    match operator {
+        AggregationOperator::Every => item_results.all(|item_result| item_result),
+        AggregationOperator::Any => item_results.any(|item_result| item_result),
+        AggregationOperator::None => !item_results.any(|item_result| item_result),
+    }
+}
+
+/// The array context of each item of an array: the given context with the array fixed to the
+/// item's index.
+fn get_item_contexts<'b, 'c>(
+    array_address: &'b str,
+    facts: &impl GetFact,
+    array_context: &'c HashMap<&'b str, i64>,
+) -> impl Iterator<Item = HashMap<&'b str, i64>> + 'c
+where
+    'b: 'c,
+{
+    let array_len = facts.get_array_len(array_address, array_context);
+
+    return (0..array_len).map(move |i| {
+        let mut item_context: HashMap<&str, i64> = HashMap::new();
+        item_context.insert(array_address, i);
+        item_context.extend(array_context);
+        item_context
+    });
+}
+
 fn get_fact_from_condition_value<'a>(
     condition_value: &ConditionValue,
     facts: &'a impl GetFact,
     array_context: &HashMap<&str, i64>,
+    environment: &Environment,
 ) -> Option<FactValue> {
     match condition_value {
         ConditionValue::Fixed(value) => Some(value.to_owned()),
         ConditionValue::Json(reference) => facts.get_fact(&reference.address, array_context),
-        ConditionValue::Transform(function) => evaluate_fn(function, facts, array_context),
+        ConditionValue::Transform(function) => {
+            evaluate_fn(function, facts, array_context, environment)
+        }
+    }
+}
+
+/// Resolve a list of values, expanding array references and array values into one fact per
+/// array item. Missing facts are skipped.
+fn get_facts_from_condition_values<'a>(
+    condition_values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Vec<FactValue> {
+    return get_optional_facts_from_condition_values(
+        condition_values,
+        facts,
+        array_context,
+        environment,
+    )
+    .into_iter()
+    .flatten()
+    .collect();
+}
+
+/// Resolve a list of values as `get_facts_from_condition_values` does, but keep a `None` in
+/// place of each missing fact, so that two lists over the same array stay aligned item by item.
+fn get_optional_facts_from_condition_values<'a>(
+    condition_values: &Vec<ConditionValue>,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    environment: &Environment,
+) -> Vec<Option<FactValue>> {
+    let mut values = Vec::new();
+
+    for condition_value in condition_values {
+        match condition_value {
+            ConditionValue::Json(reference) => {
+                get_array_facts(&reference.address, 0, facts, array_context, &mut values);
+            }
+            _ => match get_fact_from_condition_value(
+                condition_value,
+                facts,
+                array_context,
+                environment,
+            ) {
+                Some(FactValue::Array(items)) => values.extend(items.into_iter().map(Some)),
+                value => values.push(value),
+            },
+        }
+    }
+
+    return values;
+}
+
+/// Collect the facts at an address, iterating over every array (`[]`) from `search_from` on
+/// that isn't already fixed by the array context.
+fn get_array_facts<'a>(
+    address: &str,
+    search_from: usize,
+    facts: &'a impl GetFact,
+    array_context: &HashMap<&str, i64>,
+    values: &mut Vec<Option<FactValue>>,
+) {
+    let array_end = match address[search_from..].find("[]") {
+        Some(index) => search_from + index,
+        None => {
+            values.push(facts.get_fact(address, array_context));
+            return;
+        }
+    };
+
+    let array_address = &address[..array_end];
+    if array_context.contains_key(array_address) {
+        get_array_facts(address, array_end + 2, facts, array_context, values);
+        return;
+    }
+
+    let array_len = facts.get_array_len(array_address, array_context);
+    for i in 0..array_len {
+        let mut item_context: HashMap<&str, i64> = HashMap::new();
+        item_context.insert(array_address, i);
+        item_context.extend(array_context);
+
+        get_array_facts(address, array_end + 2, facts, &item_context, values);
     }
 }
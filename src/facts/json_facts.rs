@@ -1,17 +1,29 @@
 use std::collections::HashMap;
 
-use crate::facts::NumberFact;
+use crate::facts::{DateFact, NumberFact};
 use serde_json::{self, Value as JsonValue};
 
 use super::{FactValue, GetFact};
 
 pub struct JsonFacts {
     fact_object: JsonValue,
+    parse_dates: bool,
 }
 
 impl JsonFacts {
     pub fn new(facts: JsonValue) -> JsonFacts {
-        JsonFacts { fact_object: facts }
+        JsonFacts {
+            fact_object: facts,
+            parse_dates: false,
+        }
+    }
+
+    /// Like `new`, but ISO-8601 strings are returned as `FactValue::Date` facts.
+    pub fn with_date_parsing(facts: JsonValue) -> JsonFacts {
+        JsonFacts {
+            fact_object: facts,
+            parse_dates: true,
+        }
     }
 }
 
@@ -54,7 +66,13 @@ impl GetFact for JsonFacts {
         match value {
             JsonValue::Bool(bool_value) => return Some(FactValue::Boolean(*bool_value)),
             JsonValue::String(string_value) => {
-                return Some(FactValue::String(string_value.to_string()))
+                if self.parse_dates {
+                    if let Some(date_fact) = DateFact::parse(string_value) {
+                        return Some(FactValue::Date(date_fact));
+                    }
+                }
+
+                return Some(FactValue::String(string_value.to_string()));
             }
             JsonValue::Number(number_value) => {
                 if number_value.is_f64() {
@@ -127,8 +145,13 @@ fn hydrate_address_collection_indicies<'a>(
         return address.to_string();
     }
 
+    // Replace the longest array addresses first so that a nested array such as
+    // `people[].children` is hydrated before its parent `people`.
+    let mut contexts: Vec<(&&str, &i64)> = array_context.iter().collect();
+    contexts.sort_by_key(|context| std::cmp::Reverse(context.0.len()));
+
     let mut hydrated_address = address.to_string();
-    for context in array_context.iter() {
+    for context in contexts {
         let to_replace = format!("{}[]", context.0);
         let replacement = format!("{}.{}", context.0, context.1);
 
@@ -235,4 +258,46 @@ mod tests {
         let fact_one = facts.get_fact("people[].age", &array_context);
         assert!(fact_one.is_none());
     }
+
+    #[test]
+    fn get_date_fact_when_parsing_dates() {
+        let data = serde_json::json!({ "signed_up": "2022-09-30", "name": "2022" });
+        let array_context: HashMap<&str, i64> = HashMap::new();
+
+        let facts = JsonFacts::with_date_parsing(data.clone());
+        assert_eq!(
+            facts.get_fact("signed_up", &array_context).unwrap(),
+            FactValue::Date(DateFact::parse("2022-09-30").unwrap())
+        );
+        assert_eq!(
+            facts.get_fact("name", &array_context).unwrap(),
+            FactValue::String("2022".to_string())
+        );
+
+        let facts = JsonFacts::new(data);
+        assert_eq!(
+            facts.get_fact("signed_up", &array_context).unwrap(),
+            FactValue::String("2022-09-30".to_string())
+        );
+    }
+
+    #[test]
+    fn get_fact_from_nested_array_by_full_address() {
+        let data = serde_json::json!({
+            "people": [
+                { "children": [{ "child_age": 10 }, { "child_age": 12 }] },
+            ]
+        });
+        let facts = JsonFacts::new(data);
+
+        let mut array_context: HashMap<&str, i64> = HashMap::new();
+        array_context.insert("people", 0);
+        array_context.insert("people[].children", 1);
+
+        let fact_one = facts.get_fact("people[].children[].child_age", &array_context);
+        assert_eq!(
+            fact_one.unwrap(),
+            FactValue::Number(NumberFact::Integer(12))
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
 pub mod json_facts;
@@ -13,9 +14,11 @@ pub trait GetFact {
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum FactValue {
     Number(NumberFact),
-    // Date(chrono::DateTime),
+    Date(DateFact),
     String(String),
     Boolean(bool),
+    /// The values produced by the array functions, such as `Map` and `Filter`.
+    Array(Vec<FactValue>),
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -24,6 +27,75 @@ pub enum NumberFact {
     Float(f64),
 }
 
+impl NumberFact {
+    /// A float fact, or `None` for infinities and NaN.
+    pub fn from_f64(value: f64) -> Option<NumberFact> {
+        if !value.is_finite() {
+            return None;
+        }
+        return Some(NumberFact::Float(value));
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            NumberFact::Integer(value) => *value as f64,
+            NumberFact::Float(value) => *value,
+        }
+    }
+
+    /// Addition that stays an integer for two integers and returns `None` on overflow.
+    pub fn checked_add(&self, other: &NumberFact) -> Option<NumberFact> {
+        match (self, other) {
+            (NumberFact::Integer(a), NumberFact::Integer(b)) => {
+                Some(NumberFact::Integer(a.checked_add(*b)?))
+            }
+            _ => NumberFact::from_f64(self.as_f64() + other.as_f64()),
+        }
+    }
+
+    /// Subtraction that stays an integer for two integers and returns `None` on overflow.
+    pub fn checked_sub(&self, other: &NumberFact) -> Option<NumberFact> {
+        match (self, other) {
+            (NumberFact::Integer(a), NumberFact::Integer(b)) => {
+                Some(NumberFact::Integer(a.checked_sub(*b)?))
+            }
+            _ => NumberFact::from_f64(self.as_f64() - other.as_f64()),
+        }
+    }
+
+    /// Multiplication that stays an integer for two integers and returns `None` on overflow.
+    pub fn checked_mul(&self, other: &NumberFact) -> Option<NumberFact> {
+        match (self, other) {
+            (NumberFact::Integer(a), NumberFact::Integer(b)) => {
+                Some(NumberFact::Integer(a.checked_mul(*b)?))
+            }
+            _ => NumberFact::from_f64(self.as_f64() * other.as_f64()),
+        }
+    }
+
+    /// Division that returns `None` when dividing by zero. Two integers only produce an
+    /// integer when they divide exactly.
+    pub fn checked_div(&self, other: &NumberFact) -> Option<NumberFact> {
+        match (self, other) {
+            (NumberFact::Integer(a), NumberFact::Integer(b)) => {
+                if *b == 0 {
+                    return None;
+                }
+                if a.checked_rem(*b)? == 0 {
+                    return Some(NumberFact::Integer(a / b));
+                }
+                NumberFact::from_f64(*a as f64 / *b as f64)
+            }
+            _ => {
+                if other.as_f64() == 0.0 {
+                    return None;
+                }
+                NumberFact::from_f64(self.as_f64() / other.as_f64())
+            }
+        }
+    }
+}
+
 impl PartialEq for NumberFact {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -58,6 +130,71 @@ impl PartialOrd for NumberFact {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum DateFact {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+    Timestamp(DateTime<FixedOffset>),
+}
+
+impl DateFact {
+    /// Parse an ISO-8601 date (`2022-09-30`), local date-time (`2022-09-30T12:00:00`)
+    /// or offset timestamp (`2022-09-30T12:00:00-04:00`).
+    pub fn parse(text: &str) -> Option<DateFact> {
+        if let Ok(timestamp) = DateTime::parse_from_rfc3339(text) {
+            return Some(DateFact::Timestamp(timestamp));
+        }
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f") {
+            return Some(DateFact::DateTime(date_time));
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+            return Some(DateFact::Date(date));
+        }
+
+        return None;
+    }
+
+    /// The instant this fact represents. Dates are taken as midnight and
+    /// date-times without an offset are assumed to be UTC.
+    pub fn naive_utc(&self) -> NaiveDateTime {
+        match self {
+            DateFact::Date(date) => date.and_hms_opt(0, 0, 0).unwrap(),
+            DateFact::DateTime(date_time) => *date_time,
+            DateFact::Timestamp(timestamp) => timestamp.naive_utc(),
+        }
+    }
+
+    /// The wall-clock date and time, in the timestamp's own offset for timestamps.
+    pub fn naive_local(&self) -> NaiveDateTime {
+        match self {
+            DateFact::Timestamp(timestamp) => timestamp.naive_local(),
+            _ => self.naive_utc(),
+        }
+    }
+}
+
+impl PartialEq for DateFact {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DateFact::Date(a), DateFact::Date(b)) => a.eq(b),
+            (DateFact::DateTime(a), DateFact::DateTime(b)) => a.eq(b),
+            (DateFact::Timestamp(a), DateFact::Timestamp(b)) => a.eq(b),
+            _ => self.naive_utc().eq(&other.naive_utc()),
+        }
+    }
+}
+
+impl PartialOrd for DateFact {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (DateFact::Date(a), DateFact::Date(b)) => a.partial_cmp(b),
+            (DateFact::DateTime(a), DateFact::DateTime(b)) => a.partial_cmp(b),
+            (DateFact::Timestamp(a), DateFact::Timestamp(b)) => a.partial_cmp(b),
+            _ => self.naive_utc().partial_cmp(&other.naive_utc()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,4 +213,58 @@ mod tests {
         assert!(NumberFact::Float(1.5) < NumberFact::Integer(2));
         assert!(NumberFact::Float(1.0) <= NumberFact::Integer(1));
     }
+
+    #[test]
+    fn checked_arithmetic_promotion() {
+        assert!(matches!(
+            NumberFact::Integer(2).checked_mul(&NumberFact::Integer(3)),
+            Some(NumberFact::Integer(6))
+        ));
+        assert!(matches!(
+            NumberFact::Integer(2).checked_mul(&NumberFact::Float(1.5)),
+            Some(NumberFact::Float(_))
+        ));
+        assert_eq!(
+            NumberFact::Integer(7).checked_div(&NumberFact::Integer(2)),
+            Some(NumberFact::Float(3.5))
+        );
+        assert!(NumberFact::Integer(i64::MAX)
+            .checked_add(&NumberFact::Integer(1))
+            .is_none());
+        assert!(NumberFact::Float(1.0)
+            .checked_div(&NumberFact::Integer(0))
+            .is_none());
+        assert!(NumberFact::Float(f64::MAX)
+            .checked_mul(&NumberFact::Integer(2))
+            .is_none());
+    }
+
+    #[test]
+    fn parse_dates() {
+        let date = NaiveDate::from_ymd_opt(2022, 9, 30).unwrap();
+
+        assert_eq!(DateFact::parse("2022-09-30"), Some(DateFact::Date(date)));
+        assert_eq!(
+            DateFact::parse("2022-09-30T12:30:00"),
+            Some(DateFact::DateTime(date.and_hms_opt(12, 30, 0).unwrap()))
+        );
+        assert!(matches!(
+            DateFact::parse("2022-09-30T12:30:00-04:00"),
+            Some(DateFact::Timestamp(_))
+        ));
+        assert!(DateFact::parse("30/09/2022").is_none());
+    }
+
+    #[test]
+    fn date_datetime_timestamp_partial_order() {
+        let date = DateFact::parse("2022-09-30").unwrap();
+        let date_time = DateFact::parse("2022-09-30T00:00:00").unwrap();
+        let timestamp = DateFact::parse("2022-09-30T02:00:00+02:00").unwrap();
+        let later_timestamp = DateFact::parse("2022-09-30T00:00:01Z").unwrap();
+
+        assert_eq!(date, date_time);
+        assert_eq!(date, timestamp);
+        assert!(date < later_timestamp);
+        assert!(later_timestamp > date_time);
+    }
 }
@@ -1,17 +1,54 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::Deserialize;
 use serde_json;
 use wasm_bindgen::prelude::wasm_bindgen;
 extern crate console_error_panic_hook;
 
 use crate::{
-    conditions::Condition, evaluation::evaluate_with_array_context, facts::json_facts::JsonFacts,
+    conditions::Condition,
+    evaluation::{
+        environment::{Environment, FixedClock},
+        evaluate_with_environment,
+    },
+    facts::{json_facts::JsonFacts, FactValue},
     serialization::deserialize::deserialize_condition,
 };
 
 // A very inefficient (because facts and the condition must be deserialized) condition evaluation function that can be compiled into webassembly
+// It has no holiday calendars or lookup tables and uses the system clock and an unseeded random
+// source; pass them to `evaluate_condition_with_options` instead
 #[wasm_bindgen]
 pub fn evaluate_condition(condition_serialized: &str, facts_serialized: &str) -> bool {
+    return evaluate_condition_with_options(condition_serialized, facts_serialized, "{}");
+}
+
+/// Options of `evaluate_condition_with_options`, as JSON. Every field may be left out.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct EvaluationOptions {
+    /// Read ISO-8601 text in the facts as dates, as `JsonFacts::with_date_parsing` does.
+    parse_dates: bool,
+    /// The instant `Now()` and `Today()` return, such as "2022-10-03T09:00:00+02:00". The
+    /// system time when missing.
+    now: Option<DateTime<FixedOffset>>,
+    /// Seed of `Rand()` and `RandBetween()`. Seeded from the system time when missing.
+    seed: Option<u64>,
+    /// Holiday calendars by name, as lists of dates such as "2022-12-26".
+    holiday_calendars: HashMap<String, Vec<NaiveDate>>,
+    /// Lookup tables by name, as rows of serialized fact values.
+    lookup_tables: HashMap<String, Vec<Vec<FactValue>>>,
+}
+
+// As `evaluate_condition`, but with the environment and date parsing given as JSON options so the
+// date, business day, lookup and random functions can be used from webassembly
+#[wasm_bindgen]
+pub fn evaluate_condition_with_options(
+    condition_serialized: &str,
+    facts_serialized: &str,
+    options_serialized: &str,
+) -> bool {
     console_error_panic_hook::set_once();
 
     let condition_result = deserialize_condition(condition_serialized);
@@ -24,9 +61,34 @@ pub fn evaluate_condition(condition_serialized: &str, facts_serialized: &str) ->
         panic!("could not parse facts: {:?}", facts_result.err())
     }
 
+    let options_result = serde_json::from_str(options_serialized);
+    if options_result.is_err() {
+        panic!("could not parse options: {:?}", options_result.err())
+    }
+
     let condition: Condition = condition_result.unwrap();
     let facts = facts_result.unwrap();
+    let options: EvaluationOptions = options_result.unwrap();
+
+    let facts = if options.parse_dates {
+        JsonFacts::with_date_parsing(facts)
+    } else {
+        JsonFacts::new(facts)
+    };
+
+    let mut environment = Environment::new();
+    if let Some(now) = options.now {
+        environment.clock = Box::new(FixedClock { now });
+    }
+    if let Some(seed) = options.seed {
+        environment.seed_random(seed);
+    }
+    for (name, holidays) in options.holiday_calendars {
+        environment.register_holiday_calendar(&name, holidays);
+    }
+    for (name, rows) in options.lookup_tables {
+        environment.register_lookup_table(&name, rows);
+    }
 
-    let array_context: HashMap<&str, i64> = HashMap::new();
-    return evaluate_with_array_context(&condition, &JsonFacts::new(facts), &array_context);
+    return evaluate_with_environment(&condition, &facts, &environment);
 }
@@ -0,0 +1,41 @@
+//! Shorthand for the condition values the function tests are built from.
+#![allow(dead_code)]
+
+use engine::{
+    conditions::{functions::Fn, ConditionValue, ReferenceValue},
+    facts::{DateFact, FactValue, NumberFact},
+};
+
+pub fn reference(address: &str) -> ReferenceValue {
+    ReferenceValue {
+        address: address.to_string(),
+    }
+}
+
+pub fn json(address: &str) -> ConditionValue {
+    ConditionValue::Json(reference(address))
+}
+
+pub fn transform(function: Fn) -> ConditionValue {
+    ConditionValue::Transform(function)
+}
+
+pub fn text(value: &str) -> ConditionValue {
+    ConditionValue::Fixed(FactValue::String(value.to_string()))
+}
+
+pub fn integer(value: i64) -> ConditionValue {
+    ConditionValue::Fixed(FactValue::Number(NumberFact::Integer(value)))
+}
+
+pub fn float(value: f64) -> ConditionValue {
+    ConditionValue::Fixed(FactValue::Number(NumberFact::Float(value)))
+}
+
+pub fn boolean(value: bool) -> ConditionValue {
+    ConditionValue::Fixed(FactValue::Boolean(value))
+}
+
+pub fn date(value: &str) -> ConditionValue {
+    ConditionValue::Fixed(FactValue::Date(DateFact::parse(value).unwrap()))
+}
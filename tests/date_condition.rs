@@ -0,0 +1,126 @@
+use engine::{
+    conditions::{
+        operators::ComparisonOperator, Condition, ConditionValue, ReferenceValue, SingleCondition,
+    },
+    evaluation,
+    facts::{json_facts::JsonFacts, DateFact, FactValue},
+    serialization::serialize::serialize_condition,
+    wasm,
+};
+
+#[test]
+fn date_before_fixed_date_true() {
+    let facts = build_facts();
+
+    let single_condition = SingleCondition {
+        operator: ComparisonOperator::LessThan,
+        values: (
+            ConditionValue::Json(ReferenceValue {
+                address: "signed_up".to_string(),
+            }),
+            ConditionValue::Fixed(FactValue::Date(DateFact::parse("2022-01-01").unwrap())),
+        ),
+    };
+
+    let condition = Condition::Single(single_condition);
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn timestamp_after_fixed_date_time_false() {
+    let facts = build_facts();
+
+    let single_condition = SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Json(ReferenceValue {
+                address: "expires".to_string(),
+            }),
+            ConditionValue::Fixed(FactValue::Date(
+                DateFact::parse("2022-06-01T17:00:00").unwrap(),
+            )),
+        ),
+    };
+
+    let condition = Condition::Single(single_condition);
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result)
+}
+
+#[test]
+fn date_equals_midnight_timestamp() {
+    let facts = build_facts();
+
+    let single_condition = SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Json(ReferenceValue {
+                address: "signed_up".to_string(),
+            }),
+            ConditionValue::Fixed(FactValue::Date(
+                DateFact::parse("2021-12-31T00:00:00Z").unwrap(),
+            )),
+        ),
+    };
+
+    let condition = Condition::Single(single_condition);
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn dates_not_parsed_by_default() {
+    let facts = JsonFacts::new(serde_json::json!({ "signed_up": "2021-12-31" }));
+
+    let single_condition = SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Json(ReferenceValue {
+                address: "signed_up".to_string(),
+            }),
+            ConditionValue::Fixed(FactValue::Date(DateFact::parse("2021-12-31").unwrap())),
+        ),
+    };
+
+    let condition = Condition::Single(single_condition);
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result)
+}
+
+#[test]
+fn dates_parsed_through_wasm_when_asked() {
+    let single_condition = SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Json(ReferenceValue {
+                address: "signed_up".to_string(),
+            }),
+            ConditionValue::Fixed(FactValue::Date(DateFact::parse("2021-12-31").unwrap())),
+        ),
+    };
+
+    let condition = serialize_condition(&Condition::Single(single_condition)).unwrap();
+    let facts = serde_json::json!({ "signed_up": "2021-12-31" }).to_string();
+
+    assert!(!wasm::evaluate_condition(&condition, &facts));
+    assert!(!wasm::evaluate_condition_with_options(
+        &condition,
+        &facts,
+        r#"{ "parse_dates": false }"#
+    ));
+    assert!(wasm::evaluate_condition_with_options(
+        &condition,
+        &facts,
+        r#"{ "parse_dates": true }"#
+    ));
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "signed_up": "2021-12-31",
+        "expires": "2022-06-01T12:00:00-04:00",
+    });
+
+    JsonFacts::with_date_parsing(data)
+}
@@ -0,0 +1,272 @@
+mod common;
+
+use common::{boolean, float, integer, json, reference, text, transform};
+use engine::{
+    conditions::{
+        functions::{Fn, Reduction},
+        operators::{AggregationOperator, ComparisonOperator},
+        Condition, ConditionGroup, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::{json_facts::JsonFacts, FactValue, NumberFact},
+};
+
+#[test]
+fn order_total_from_line_items() {
+    let facts = build_facts();
+
+    let line_totals = transform(Fn::Map(
+        reference("orders"),
+        Box::new(transform(Fn::Product(vec![
+            json("orders[].quantity"),
+            json("orders[].price"),
+        ]))),
+    ));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (transform(Fn::Sum(vec![line_totals])), float(114.5)),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn filter_and_reduce() {
+    let facts = build_facts();
+
+    let shipped_prices = || {
+        transform(Fn::Filter(
+            reference("orders"),
+            Box::new(equals(json("orders[].status"), text("shipped"))),
+            Some(Box::new(json("orders[].price"))),
+        ))
+    };
+
+    let cases = vec![
+        (
+            shipped_prices(),
+            ConditionValue::Fixed(FactValue::Array(vec![
+                FactValue::Number(NumberFact::Float(12.5)),
+                FactValue::Number(NumberFact::Integer(40)),
+            ])),
+        ),
+        (
+            transform(Fn::Reduce(Reduction::Sum, vec![shipped_prices()])),
+            float(52.5),
+        ),
+        (
+            transform(Fn::Reduce(Reduction::Max, vec![shipped_prices()])),
+            integer(40),
+        ),
+        (
+            transform(Fn::Reduce(
+                Reduction::Min,
+                vec![shipped_prices(), integer(100)],
+            )),
+            float(12.5),
+        ),
+        (transform(Fn::Count(vec![shipped_prices()])), integer(2)),
+        (
+            transform(Fn::Filter(
+                reference("scores"),
+                Box::new(Condition::Single(SingleCondition {
+                    operator: ComparisonOperator::GreaterThanEqualTo,
+                    values: (json("scores[]"), integer(50)),
+                })),
+                None,
+            )),
+            ConditionValue::Fixed(FactValue::Array(vec![
+                FactValue::Number(NumberFact::Integer(72)),
+                FactValue::Number(NumberFact::Integer(50)),
+            ])),
+        ),
+    ];
+
+    for (value, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (value, expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn any_and_all_within_a_group() {
+    let facts = build_facts();
+
+    let condition = |status: &str| {
+        Condition::Group(ConditionGroup {
+            operator: AggregationOperator::Every,
+            conditions: vec![
+                equals(
+                    transform(Fn::All(
+                        reference("orders"),
+                        Box::new(Condition::Single(SingleCondition {
+                            operator: ComparisonOperator::GreaterThan,
+                            values: (json("orders[].quantity"), integer(0)),
+                        })),
+                    )),
+                    boolean(true),
+                ),
+                equals(
+                    transform(Fn::Any(
+                        reference("orders"),
+                        Box::new(equals(json("orders[].status"), text(status))),
+                    )),
+                    boolean(true),
+                ),
+            ],
+        })
+    };
+
+    assert!(evaluation::evaluate(&condition("pending"), &facts));
+    assert!(!evaluation::evaluate(&condition("cancelled"), &facts));
+}
+
+#[test]
+fn nested_arrays_use_the_outer_item() {
+    let facts = build_facts();
+
+    let vip_customers = transform(Fn::Filter(
+        reference("customers"),
+        Box::new(equals(
+            transform(Fn::Any(
+                reference("customers[].orders"),
+                Box::new(Condition::Single(SingleCondition {
+                    operator: ComparisonOperator::GreaterThan,
+                    values: (json("customers[].orders[].total"), integer(1000)),
+                })),
+            )),
+            boolean(true),
+        )),
+        Some(Box::new(json("customers[].name"))),
+    ));
+
+    let condition = equals(
+        vip_customers,
+        ConditionValue::Fixed(FactValue::Array(vec![FactValue::String(
+            "Grace".to_string(),
+        )])),
+    );
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn empty_arrays() {
+    let facts = build_facts();
+
+    let empty = || {
+        transform(Fn::Map(
+            reference("missing"),
+            Box::new(json("missing[].price")),
+        ))
+    };
+
+    let condition = equals(
+        transform(Fn::Reduce(Reduction::Sum, vec![empty()])),
+        integer(0),
+    );
+    assert!(evaluation::evaluate(&condition, &facts));
+
+    let condition = equals(
+        transform(Fn::IsBlank(Box::new(transform(Fn::Reduce(
+            Reduction::Max,
+            vec![empty()],
+        ))))),
+        boolean(true),
+    );
+    assert!(evaluation::evaluate(&condition, &facts));
+
+    let condition = equals(
+        transform(Fn::All(
+            reference("missing"),
+            Box::new(equals(json("missing[].price"), integer(0))),
+        )),
+        boolean(true),
+    );
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn distinct_and_sorted_values() {
+    let facts = build_facts();
+
+    let countries = || vec![json("shipments[].country")];
+    let array = |items: Vec<FactValue>| ConditionValue::Fixed(FactValue::Array(items));
+    let country = |code: &str| FactValue::String(code.to_string());
+
+    let cases = vec![
+        (
+            transform(Fn::CountA(vec![transform(Fn::Unique(countries()))])),
+            integer(3),
+        ),
+        (
+            transform(Fn::Unique(countries())),
+            array(vec![country("FR"), country("DE"), country("NL")]),
+        ),
+        (
+            transform(Fn::Sort(vec![transform(Fn::Unique(countries()))], None)),
+            array(vec![country("DE"), country("FR"), country("NL")]),
+        ),
+        (
+            transform(Fn::Sort(
+                vec![json("scores[]"), integer(40)],
+                Some(Box::new(integer(-1))),
+            )),
+            array(vec![
+                FactValue::Number(NumberFact::Integer(72)),
+                FactValue::Number(NumberFact::Integer(50)),
+                FactValue::Number(NumberFact::Integer(40)),
+                FactValue::Number(NumberFact::Integer(31)),
+            ]),
+        ),
+    ];
+
+    for (value, expected) in cases {
+        assert!(evaluation::evaluate(&equals(value, expected), &facts));
+    }
+
+    let condition = equals(
+        transform(Fn::IsBlank(Box::new(transform(Fn::Sort(
+            countries(),
+            Some(Box::new(integer(0))),
+        ))))),
+        boolean(true),
+    );
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "orders": [
+            { "quantity": 2, "price": 12.5, "status": "shipped" },
+            { "quantity": 1, "price": 40, "status": "shipped" },
+            { "quantity": 3, "price": 16.5, "status": "pending" },
+        ],
+        "scores": [72, 31, 50],
+        "shipments": [
+            { "country": "FR" },
+            { "country": "DE" },
+            { "country": "FR" },
+            { "country": "NL" },
+        ],
+        "customers": [
+            { "name": "Ada", "orders": [{ "total": 250 }, { "total": 900 }] },
+            { "name": "Grace", "orders": [{ "total": 1200 }] },
+        ],
+    });
+
+    JsonFacts::new(data)
+}
+
+fn equals(a: ConditionValue, b: ConditionValue) -> Condition {
+    Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (a, b),
+    })
+}
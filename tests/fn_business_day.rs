@@ -0,0 +1,193 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::{boolean, date, integer, json, text};
+use engine::{
+    conditions::{
+        functions::{Fn, Holidays},
+        operators::ComparisonOperator,
+        Condition, ConditionValue, ReferenceValue, SingleCondition,
+    },
+    evaluation::{self, environment::Environment},
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn net_work_days_skips_weekends() {
+    let facts = build_facts();
+
+    // Monday 2022-10-03 to Monday 2022-10-17.
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::NetWorkDays(
+                Box::new(json("opened")),
+                Box::new(date("2022-10-17")),
+                None,
+            )),
+            integer(11),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn net_work_days_with_registered_calendar() {
+    let facts = build_facts();
+    let mut environment = Environment::new();
+    environment.register_holiday_calendar(
+        "canada",
+        vec![NaiveDate::from_ymd_opt(2022, 10, 10).unwrap()],
+    );
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::NetWorkDays(
+                Box::new(json("opened")),
+                Box::new(date("2022-10-17")),
+                Some(Holidays::Calendar("canada".to_string())),
+            )),
+            integer(10),
+        ),
+    });
+
+    let result = evaluation::evaluate_with_environment(&condition, &facts, &environment);
+    assert!(result)
+}
+
+#[test]
+fn unknown_calendar_is_not_equal() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::NetWorkDays(
+                Box::new(json("opened")),
+                Box::new(date("2022-10-17")),
+                Some(Holidays::Calendar("missing".to_string())),
+            )),
+            integer(11),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result)
+}
+
+#[test]
+fn net_work_days_intl_with_weekend_mask() {
+    let facts = build_facts();
+
+    // Friday and Saturday weekend.
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::NetWorkDaysIntl(
+                Box::new(json("opened")),
+                Box::new(date("2022-10-09")),
+                Some(Box::new(text("0000110"))),
+                None,
+            )),
+            integer(5),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn due_within_five_business_days() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::LessThanEqualTo,
+        values: (
+            json("resolved"),
+            ConditionValue::Transform(Fn::WorkDay(
+                Box::new(json("opened")),
+                Box::new(integer(5)),
+                Some(Holidays::Json(ReferenceValue {
+                    address: "holidays".to_string(),
+                })),
+            )),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn work_day_intl_backwards() {
+    let facts = build_facts();
+
+    // Sunday only weekend.
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::WorkDayIntl(
+                Box::new(json("opened")),
+                Box::new(integer(-2)),
+                Some(Box::new(integer(11))),
+                None,
+            )),
+            date("2022-09-30"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn work_day_skips_whole_weeks() {
+    let facts = build_facts();
+
+    let work_day = |days: i64, expected: &str| {
+        Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::WorkDay(
+                    Box::new(json("opened")),
+                    Box::new(integer(days)),
+                    Some(Holidays::Json(ReferenceValue {
+                        address: "holidays".to_string(),
+                    })),
+                )),
+                date(expected),
+            ),
+        })
+    };
+
+    // 2022-10-10 is a holiday, so ten work days from Monday 2022-10-03 end on a Tuesday.
+    assert!(evaluation::evaluate(&work_day(10, "2022-10-18"), &facts));
+    assert!(evaluation::evaluate(&work_day(-10, "2022-09-19"), &facts));
+    assert!(evaluation::evaluate(&work_day(260, "2023-10-03"), &facts));
+
+    // Too far to be a date, but answered without walking every day.
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                Fn::WorkDay(Box::new(json("opened")), Box::new(integer(i64::MAX)), None),
+            )))),
+            boolean(true),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "opened": "2022-10-03",
+        "resolved": "2022-10-11",
+        "holidays": ["2022-10-10"],
+    });
+
+    JsonFacts::with_date_parsing(data)
+}
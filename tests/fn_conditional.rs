@@ -0,0 +1,184 @@
+mod common;
+
+use chrono::DateTime;
+use common::{date, float, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn,
+        operators::{AggregationOperator, ComparisonOperator},
+        Condition, ConditionGroup, ConditionValue, ReferenceValue, SingleCondition,
+    },
+    evaluation::{
+        self,
+        environment::{Environment, FixedClock},
+    },
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn refunds_in_last_thirty_days_over_limit() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::SumIf(
+                orders(),
+                Box::new(recent_refund()),
+                Box::new(json("orders[].amount")),
+            )),
+            integer(200),
+        ),
+    });
+
+    let result = evaluation::evaluate_with_environment(&condition, &facts, &build_environment());
+    assert!(result)
+}
+
+#[test]
+fn conditional_aggregates() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::SumIf(
+                orders(),
+                Box::new(recent_refund()),
+                Box::new(json("orders[].amount")),
+            ),
+            float(240.5),
+        ),
+        (Fn::CountIf(orders(), Box::new(refunded())), integer(3)),
+        (
+            Fn::AverageIf(
+                orders(),
+                Box::new(refunded()),
+                Box::new(json("orders[].amount")),
+            ),
+            float(110.5),
+        ),
+        (
+            Fn::MaxIfs(
+                orders(),
+                Box::new(refunded()),
+                Box::new(json("orders[].amount")),
+            ),
+            float(200.5),
+        ),
+        (
+            Fn::MinIfs(
+                orders(),
+                Box::new(refunded()),
+                Box::new(json("orders[].date")),
+            ),
+            date("2022-06-01"),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate_with_environment(
+            &condition,
+            &facts,
+            &build_environment()
+        ));
+    }
+}
+
+#[test]
+fn no_matching_items() {
+    let facts = build_facts();
+
+    let never = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (json("orders[].status"), text("lost")),
+    });
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::SumIf(
+                orders(),
+                Box::new(never),
+                Box::new(json("orders[].amount")),
+            )),
+            integer(0),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+
+    let never = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (json("orders[].status"), text("lost")),
+    });
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::AverageIf(
+                orders(),
+                Box::new(never),
+                Box::new(json("orders[].amount")),
+            )),
+            integer(0),
+        ),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+}
+
+fn refunded() -> Condition {
+    Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (json("orders[].status"), text("refunded")),
+    })
+}
+
+fn recent_refund() -> Condition {
+    Condition::Group(ConditionGroup {
+        operator: AggregationOperator::Every,
+        conditions: vec![
+            refunded(),
+            Condition::Single(SingleCondition {
+                operator: ComparisonOperator::LessThanEqualTo,
+                values: (
+                    ConditionValue::Transform(Fn::Days(
+                        Box::new(ConditionValue::Transform(Fn::Today)),
+                        Box::new(json("orders[].date")),
+                    )),
+                    integer(30),
+                ),
+            }),
+        ],
+    })
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "orders": [
+            { "date": "2022-09-20", "status": "refunded", "amount": 200.5 },
+            { "date": "2022-09-25", "status": "shipped", "amount": 75 },
+            { "date": "2022-09-28", "status": "refunded", "amount": 40 },
+            { "date": "2022-06-01", "status": "refunded", "amount": 91 },
+        ],
+    });
+
+    JsonFacts::with_date_parsing(data)
+}
+
+fn build_environment() -> Environment {
+    Environment {
+        clock: Box::new(FixedClock {
+            now: DateTime::parse_from_rfc3339("2022-09-30T12:00:00Z").unwrap(),
+        }),
+        ..Environment::new()
+    }
+}
+
+fn orders() -> ReferenceValue {
+    ReferenceValue {
+        address: "orders".to_string(),
+    }
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use common::{boolean, float, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn numbers_carried_as_text_compare_as_numbers() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (json("order.total"), float(1234.5)),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Value(Box::new(json("order.total")))),
+            float(1234.5),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn value_and_number_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (Fn::Value(Box::new(json("order.total"))), float(1234.5)),
+        (Fn::Value(Box::new(text(" 42 "))), integer(42)),
+        (Fn::Value(Box::new(text("-7"))), integer(-7)),
+        (Fn::Value(Box::new(text("12.5%"))), float(0.125)),
+        (Fn::Value(Box::new(text("1.5E3"))), integer(1500)),
+        (Fn::Value(Box::new(json("order.placed"))), integer(44834)),
+        (Fn::Value(Box::new(integer(3))), integer(3)),
+        (
+            Fn::NumberValue(
+                Box::new(json("order.total_eu")),
+                Some(Box::new(text(","))),
+                Some(Box::new(text("."))),
+            ),
+            float(1234.5),
+        ),
+        (
+            Fn::NumberValue(Box::new(text("2 500 000")), None, None),
+            integer(2_500_000),
+        ),
+        (
+            Fn::NumberValue(Box::new(text(".5")), None, None),
+            float(0.5),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn text_that_is_not_a_number_has_no_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        Fn::Value(Box::new(text("12abc"))),
+        Fn::Value(Box::new(text("1.2.3"))),
+        Fn::Value(Box::new(text("1.234,5"))),
+        Fn::Value(Box::new(text(""))),
+        Fn::Value(Box::new(text("%"))),
+        Fn::Value(Box::new(boolean(true))),
+        Fn::NumberValue(
+            Box::new(text("1,5")),
+            Some(Box::new(text(","))),
+            Some(Box::new(text(","))),
+        ),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn text_formats() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (float(1234.5), "#,##0.00", "1,234.50"),
+        (integer(1234567), "#,##0", "1,234,567"),
+        (integer(-1234), "$#,##0", "-$1,234"),
+        (float(0.256), "0.0%", "25.6%"),
+        (float(1.23456), "0.###", "1.235"),
+        (float(0.5), "#.00", ".50"),
+        (integer(5), "000", "005"),
+        (float(2.5), "0", "3"),
+        (float(-0.001), "0.00", "0.00"),
+        (integer(12), "0 \"items\"", "12 items"),
+        (json("order.placed"), "dd/mm/yyyy", "30/09/2022"),
+        (json("order.placed"), "d mmmm yyyy", "30 September 2022"),
+        (
+            json("order.shipped"),
+            "yyyy-mm-dd hh:mm",
+            "2022-10-03 14:05",
+        ),
+        (json("order.shipped"), "h:mm:ss", "14:05:09"),
+        (json("order.total"), "0.00", "1,234.50"),
+        (boolean(true), "0", "TRUE"),
+    ];
+
+    for (value, format_text, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Text(Box::new(value), Box::new(text(format_text)))),
+                text(expected),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn to_boolean() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (json("order.gift"), Some(true)),
+        (text(" Yes "), Some(true)),
+        (text("false"), Some(false)),
+        (text("0"), Some(false)),
+        (integer(2), Some(true)),
+        (float(0.0), Some(false)),
+        (text("maybe"), None),
+        (json("order.placed"), None),
+    ];
+
+    for (value, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::ToBoolean(Box::new(value))),
+                match expected {
+                    Some(expected) => boolean(expected),
+                    None => json("missing"),
+                },
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "order": {
+            "total": "1,234.50",
+            "total_eu": "1.234,50",
+            "gift": "TRUE",
+            "placed": "2022-09-30",
+            "shipped": "2022-10-03T14:05:09",
+        },
+    });
+
+    JsonFacts::with_date_parsing(data)
+}
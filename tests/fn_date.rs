@@ -0,0 +1,220 @@
+mod common;
+
+use chrono::DateTime;
+use common::{boolean, date, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation::{
+        self,
+        environment::{Environment, FixedClock},
+    },
+    facts::{json_facts::JsonFacts, FactValue, NumberFact},
+};
+
+#[test]
+fn age_in_years_from_birth_date() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::DateDif(
+                Box::new(json("birth_date")),
+                Box::new(ConditionValue::Transform(Fn::Today)),
+                Box::new(text("Y")),
+            )),
+            integer(32),
+        ),
+    });
+
+    let result = evaluation::evaluate_with_environment(&condition, &facts, &build_environment());
+    assert!(result)
+}
+
+#[test]
+fn signed_up_within_last_thirty_days() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::LessThanEqualTo,
+        values: (
+            ConditionValue::Transform(Fn::Days(
+                Box::new(ConditionValue::Transform(Fn::Now)),
+                Box::new(json("signed_up")),
+            )),
+            integer(30),
+        ),
+    });
+
+    let result = evaluation::evaluate_with_environment(&condition, &facts, &build_environment());
+    assert!(result)
+}
+
+#[test]
+fn end_of_month_after_invoice_date() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::EOMonth(
+                Box::new(json("invoice_date")),
+                Box::new(integer(1)),
+            )),
+            date("2022-02-28"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn edate_clamps_to_end_of_month() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::EDate(
+                Box::new(json("invoice_date")),
+                Box::new(integer(1)),
+            )),
+            date("2022-02-28"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn date_rolls_over_months_and_days() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Date(
+                Box::new(integer(2022)),
+                Box::new(integer(14)),
+                Box::new(integer(0)),
+            )),
+            date("2023-01-31"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn date_out_of_range_is_not_a_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (2024, i64::MIN, 1),
+        (2024, 1, i64::MIN),
+        (2024, i64::MAX, 1),
+        (2024, 1, i64::MAX),
+        (i64::MAX, 1, 1),
+    ];
+
+    for (year, month, day) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    Fn::Date(
+                        Box::new(integer(year)),
+                        Box::new(integer(month)),
+                        Box::new(integer(day)),
+                    ),
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn week_day_and_week_num() {
+    let facts = build_facts();
+
+    // 2022-01-31 is a Monday in the sixth Sunday-started week of the year.
+    let week_day_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::WeekDay(Box::new(json("invoice_date")), None)),
+            integer(2),
+        ),
+    });
+    assert!(evaluation::evaluate(&week_day_condition, &facts));
+
+    let week_num_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::WeekNum(Box::new(json("invoice_date")), None)),
+            integer(6),
+        ),
+    });
+    assert!(evaluation::evaluate(&week_num_condition, &facts));
+}
+
+#[test]
+fn year_frac_actual_360() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::YearFrac(
+                Box::new(date("2022-01-01")),
+                Box::new(date("2022-01-31")),
+                Some(Box::new(integer(2))),
+            )),
+            ConditionValue::Fixed(FactValue::Number(NumberFact::Float(30.0 / 360.0))),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn hour_of_timestamp_uses_its_offset() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Hour(Box::new(json("signed_up")))),
+            integer(9),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "birth_date": "1990-03-15",
+        "signed_up": "2022-09-10T09:15:00-04:00",
+        "invoice_date": "2022-01-31",
+    });
+
+    JsonFacts::with_date_parsing(data)
+}
+
+fn build_environment() -> Environment {
+    Environment {
+        clock: Box::new(FixedClock {
+            now: DateTime::parse_from_rfc3339("2022-09-30T12:00:00Z").unwrap(),
+        }),
+        ..Environment::new()
+    }
+}
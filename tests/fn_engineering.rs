@@ -0,0 +1,184 @@
+mod common;
+
+use common::{boolean, float, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn permission_bit_is_set() {
+    let facts = build_facts();
+
+    let has_permission = |bit: i64| {
+        Condition::Single(SingleCondition {
+            operator: ComparisonOperator::GreaterThan,
+            values: (
+                ConditionValue::Transform(Fn::BitAnd(
+                    Box::new(json("permissions")),
+                    Box::new(ConditionValue::Transform(Fn::BitLShift(
+                        Box::new(integer(1)),
+                        Box::new(integer(bit)),
+                    ))),
+                )),
+                integer(0),
+            ),
+        })
+    };
+
+    assert!(evaluation::evaluate(&has_permission(0), &facts));
+    assert!(!evaluation::evaluate(&has_permission(1), &facts));
+    assert!(evaluation::evaluate(&has_permission(2), &facts));
+    assert!(evaluation::evaluate(&has_permission(40), &facts));
+}
+
+#[test]
+fn bitwise_functions() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::BitAnd(Box::new(integer(13)), Box::new(integer(25))),
+            integer(9),
+        ),
+        (
+            Fn::BitOr(Box::new(integer(23)), Box::new(integer(10))),
+            integer(31),
+        ),
+        (
+            Fn::BitXOr(Box::new(integer(5)), Box::new(integer(3))),
+            integer(6),
+        ),
+        (
+            Fn::BitLShift(Box::new(integer(4)), Box::new(integer(2))),
+            integer(16),
+        ),
+        (
+            Fn::BitRShift(Box::new(integer(13)), Box::new(integer(2))),
+            integer(3),
+        ),
+        (
+            Fn::BitRShift(Box::new(integer(13)), Box::new(integer(-2))),
+            integer(52),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn invalid_bitwise_arguments_are_not_a_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        Fn::BitAnd(Box::new(integer(-1)), Box::new(integer(1))),
+        Fn::BitOr(Box::new(float(1.5)), Box::new(integer(1))),
+        Fn::BitLShift(Box::new(integer(1)), Box::new(integer(63))),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::GreaterThan,
+            values: (ConditionValue::Transform(function), integer(-1)),
+        });
+        assert!(!evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn base_conversions() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (Fn::Bin2Dec(Box::new(text("1100100"))), integer(100)),
+        (Fn::Bin2Dec(Box::new(integer(1010))), integer(10)),
+        (Fn::Bin2Dec(Box::new(text("1111111111"))), integer(-1)),
+        (
+            Fn::Bin2Hex(Box::new(text("11111011")), Some(Box::new(integer(4)))),
+            text("00FB"),
+        ),
+        (Fn::Bin2Oct(Box::new(text("1001")), None), text("11")),
+        (
+            Fn::Dec2Bin(Box::new(integer(9)), Some(Box::new(integer(4)))),
+            text("1001"),
+        ),
+        (
+            Fn::Dec2Bin(Box::new(integer(-100)), None),
+            text("1110011100"),
+        ),
+        (Fn::Dec2Hex(Box::new(json("color")), None), text("FF8000")),
+        (
+            Fn::Dec2Hex(Box::new(integer(-54)), None),
+            text("FFFFFFFFCA"),
+        ),
+        (Fn::Dec2Oct(Box::new(integer(58)), None), text("72")),
+        (
+            Fn::Hex2Bin(Box::new(text("F")), Some(Box::new(integer(8)))),
+            text("00001111"),
+        ),
+        (Fn::Hex2Dec(Box::new(text("a5"))), integer(165)),
+        (Fn::Hex2Dec(Box::new(text("FFFFFFFF5B"))), integer(-165)),
+        (Fn::Hex2Oct(Box::new(text("3B4E")), None), text("35516")),
+        (
+            Fn::Oct2Bin(Box::new(text("3")), Some(Box::new(integer(3)))),
+            text("011"),
+        ),
+        (Fn::Oct2Dec(Box::new(text("54"))), integer(44)),
+        (
+            Fn::Oct2Hex(Box::new(text("100")), Some(Box::new(integer(4)))),
+            text("0040"),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn invalid_base_conversions_are_not_a_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        Fn::Bin2Dec(Box::new(text("102"))),
+        Fn::Bin2Dec(Box::new(text("10000000000"))),
+        Fn::Dec2Bin(Box::new(integer(512)), None),
+        Fn::Dec2Bin(Box::new(integer(9)), Some(Box::new(integer(3)))),
+        Fn::Hex2Dec(Box::new(text("-1"))),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "permissions": 1_099_511_627_781_i64,
+        "color": 16_744_448,
+    });
+
+    JsonFacts::new(data)
+}
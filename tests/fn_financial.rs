@@ -0,0 +1,247 @@
+mod common;
+
+use common::{boolean, float, integer, json};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn loan_payment_within_affordability() {
+    let facts = build_facts();
+
+    // The monthly repayment (a negative cash flow) is no more than 30% of monthly income.
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThanEqualTo,
+        values: (
+            ConditionValue::Transform(Fn::Pmt(
+                Box::new(ConditionValue::Transform(Fn::Divide(
+                    Box::new(json("loan.annual_rate")),
+                    Box::new(integer(12)),
+                ))),
+                Box::new(json("loan.months")),
+                Box::new(json("loan.amount")),
+                None,
+                None,
+            )),
+            ConditionValue::Transform(Fn::Product(vec![json("monthly_income"), float(-0.3)])),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn annuity_functions() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::Pmt(
+                Box::new(float(0.08 / 12.0)),
+                Box::new(integer(120)),
+                Box::new(integer(10000)),
+                None,
+                None,
+            ),
+            float(-121.33),
+        ),
+        (
+            Fn::Fv(
+                Box::new(float(0.06 / 12.0)),
+                Box::new(integer(10)),
+                Box::new(integer(-200)),
+                Some(Box::new(integer(-500))),
+                Some(Box::new(integer(1))),
+            ),
+            float(2581.4),
+        ),
+        (
+            Fn::Pv(
+                Box::new(float(0.08 / 12.0)),
+                Box::new(integer(240)),
+                Box::new(integer(500)),
+                None,
+                None,
+            ),
+            float(-59777.15),
+        ),
+        (
+            Fn::Nper(
+                Box::new(float(0.01)),
+                Box::new(integer(-100)),
+                Box::new(integer(-1000)),
+                Some(Box::new(integer(10000))),
+                Some(Box::new(integer(1))),
+            ),
+            float(59.67),
+        ),
+        (
+            Fn::Npv(
+                Box::new(float(0.1)),
+                vec![integer(-10000), integer(3000), integer(4200), integer(6800)],
+            ),
+            float(1188.44),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        assert!(evaluation::evaluate(
+            &rounded_equals(function, 2, expected),
+            &facts
+        ));
+    }
+}
+
+#[test]
+fn solved_rates() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::Irr(vec![json("project.cash_flows[]")], None),
+            float(0.0866),
+        ),
+        (
+            Fn::Rate(
+                Box::new(integer(48)),
+                Box::new(integer(-200)),
+                Box::new(integer(8000)),
+                None,
+                None,
+                None,
+            ),
+            float(0.0077),
+        ),
+        (
+            Fn::Xirr(
+                vec![json("investment[].amount")],
+                vec![json("investment[].date")],
+                None,
+            ),
+            float(0.3734),
+        ),
+        (
+            Fn::Xnpv(
+                Box::new(float(0.09)),
+                vec![json("investment[].amount")],
+                vec![json("investment[].date")],
+            ),
+            float(2086.6476),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        assert!(evaluation::evaluate(
+            &rounded_equals(function, 4, expected),
+            &facts
+        ));
+    }
+}
+
+#[test]
+fn rate_without_solution_is_not_a_value() {
+    let facts = build_facts();
+
+    // Every discount rate leaves these flows negative, so there is no rate of return.
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Irr(
+                vec![integer(-100), integer(50), integer(-100)],
+                None,
+            )),
+            integer(-1),
+        ),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+
+    // Cash flows in one direction have no rate of return either.
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Irr(vec![integer(100), integer(50)], None)),
+            integer(-1),
+        ),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn dated_cash_flow_with_a_missing_date_is_not_a_value() {
+    let facts = JsonFacts::new(serde_json::json!({
+        "investment": [
+            { "date": "2008-01-01", "amount": -10000 },
+            { "amount": 2750 },
+            { "date": "2008-10-30", "amount": 4250 },
+            { "date": "2009-02-15", "amount": 3250 },
+            { "date": "2009-04-01" },
+        ],
+    }));
+
+    let cases = vec![
+        Fn::Xnpv(
+            Box::new(float(0.09)),
+            vec![json("investment[].amount")],
+            vec![json("investment[].date")],
+        ),
+        Fn::Xirr(
+            vec![json("investment[].amount")],
+            vec![json("investment[].date")],
+            None,
+        ),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn rounded_equals(function: Fn, num_digits: i64, expected: ConditionValue) -> Condition {
+    Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Round(
+                Box::new(ConditionValue::Transform(function)),
+                Box::new(integer(num_digits)),
+            )),
+            expected,
+        ),
+    })
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "monthly_income": 4200,
+        "loan": {
+            "amount": 25000,
+            "annual_rate": 0.065,
+            "months": 60,
+        },
+        "project": {
+            "cash_flows": [-70000, 12000, 15000, 18000, 21000, 26000],
+        },
+        "investment": [
+            { "date": "2008-01-01", "amount": -10000 },
+            { "date": "2008-03-01", "amount": 2750 },
+            { "date": "2008-10-30", "amount": 4250 },
+            { "date": "2009-02-15", "amount": 3250 },
+            { "date": "2009-04-01", "amount": 2750 },
+        ],
+    });
+
+    JsonFacts::new(data)
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{boolean, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn,
+        operators::{AggregationOperator, ComparisonOperator},
+        Condition, ConditionGroup, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn phone_is_text_and_not_blank() {
+    let facts = build_facts();
+
+    let condition = |address: &str| {
+        Condition::Group(ConditionGroup {
+            operator: AggregationOperator::Every,
+            conditions: vec![
+                Condition::Single(SingleCondition {
+                    operator: ComparisonOperator::Equals,
+                    values: (
+                        ConditionValue::Transform(Fn::IsText(Box::new(json(address)))),
+                        boolean(true),
+                    ),
+                }),
+                Condition::Single(SingleCondition {
+                    operator: ComparisonOperator::Equals,
+                    values: (
+                        ConditionValue::Transform(Fn::IsBlank(Box::new(json(address)))),
+                        boolean(false),
+                    ),
+                }),
+            ],
+        })
+    };
+
+    assert!(evaluation::evaluate(&condition("phone"), &facts));
+    assert!(!evaluation::evaluate(&condition("fax"), &facts));
+    assert!(!evaluation::evaluate(&condition("zip_code"), &facts));
+    assert!(!evaluation::evaluate(&condition("missing"), &facts));
+}
+
+#[test]
+fn type_checks() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (Fn::IsNumber(Box::new(json("zip_code"))), boolean(true)),
+        (Fn::IsNumber(Box::new(json("phone"))), boolean(false)),
+        (Fn::IsText(Box::new(json("phone"))), boolean(true)),
+        (Fn::IsLogical(Box::new(json("verified"))), boolean(true)),
+        (Fn::IsLogical(Box::new(json("missing"))), boolean(false)),
+        (Fn::IsBlank(Box::new(json("fax"))), boolean(true)),
+        (Fn::IsBlank(Box::new(json("missing"))), boolean(true)),
+        (Fn::IsBlank(Box::new(text(""))), boolean(false)),
+        (Fn::Type(Box::new(json("zip_code"))), integer(1)),
+        (Fn::Type(Box::new(json("joined"))), integer(1)),
+        (Fn::Type(Box::new(json("phone"))), integer(2)),
+        (Fn::Type(Box::new(json("verified"))), integer(4)),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn type_of_missing_value() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Type(Box::new(json("missing")))),
+            integer(0),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result)
+}
+
+#[test]
+fn n_converts_to_numbers() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (json("zip_code"), integer(90210)),
+        (json("verified"), integer(1)),
+        (json("phone"), integer(0)),
+        (json("missing"), integer(0)),
+        (json("joined"), integer(44834)),
+    ];
+
+    for (value, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(Fn::N(Box::new(value))), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "phone": "+1 555 0100",
+        "fax": null,
+        "zip_code": 90210,
+        "verified": true,
+        "joined": "2022-09-30",
+    });
+
+    JsonFacts::with_date_parsing(data)
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use common::{boolean, float, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn tiered_discount_rate() {
+    let facts = build_facts();
+
+    let discount_rate = |total: &str| {
+        Fn::If(
+            Box::new(ConditionValue::Transform(Fn::GreaterThan(vec![
+                json(total),
+                integer(1000),
+            ]))),
+            Box::new(float(0.15)),
+            Some(Box::new(ConditionValue::Transform(Fn::If(
+                Box::new(ConditionValue::Transform(Fn::GreaterThan(vec![
+                    json(total),
+                    integer(500),
+                ]))),
+                Box::new(float(0.1)),
+                Some(Box::new(integer(0))),
+            )))),
+        )
+    };
+
+    let cases = vec![
+        (discount_rate("large_total"), float(0.15)),
+        (discount_rate("medium_total"), float(0.1)),
+        (discount_rate("small_total"), integer(0)),
+        (discount_rate("missing"), integer(0)),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn if_without_false_value() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::If(
+                Box::new(json("is_member")),
+                Box::new(text("member")),
+                None,
+            )),
+            boolean(false),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn fallback_address_when_missing() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Coalesce(vec![
+                json("shipping_address.city"),
+                json("billing_address.city"),
+                text("Unknown"),
+            ])),
+            text("Lyon"),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::IfNull(
+                Box::new(json("shipping_address.country")),
+                Box::new(text("FR")),
+            )),
+            text("FR"),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn switch_on_tier() {
+    let facts = build_facts();
+
+    let switch = |default: Option<Box<ConditionValue>>| {
+        Fn::Switch(
+            Box::new(json("tier")),
+            vec![
+                (text("bronze"), integer(1)),
+                (text("silver"), integer(2)),
+                (text("gold"), integer(3)),
+            ],
+            default,
+        )
+    };
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (ConditionValue::Transform(switch(None)), integer(2)),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+
+    let facts = JsonFacts::new(serde_json::json!({ "tier": "platinum" }));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (ConditionValue::Transform(switch(None)), integer(0)),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(switch(Some(Box::new(integer(0))))),
+            integer(0),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn xor_counts_true_values() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (vec![boolean(true), boolean(false)], true),
+        (vec![boolean(true), boolean(true)], false),
+        (vec![boolean(true), boolean(true), boolean(true)], true),
+        (vec![json("is_member"), json("missing")], false),
+    ];
+
+    for (values, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Xor(values)),
+                boolean(expected),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "large_total": 1500,
+        "medium_total": 750.5,
+        "small_total": 20,
+        "is_member": false,
+        "tier": "silver",
+        "billing_address": {
+            "city": "Lyon",
+        },
+    });
+
+    JsonFacts::new(data)
+}
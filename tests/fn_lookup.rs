@@ -0,0 +1,244 @@
+mod common;
+
+use common::{boolean, float, integer, json, text};
+use engine::{
+    conditions::{
+        functions::{Fn, Table},
+        operators::ComparisonOperator,
+        Condition, ConditionValue, SingleCondition,
+    },
+    evaluation::{self, environment::Environment},
+    facts::{json_facts::JsonFacts, FactValue, NumberFact},
+    serialization::{deserialize::deserialize_condition, serialize::serialize_condition},
+};
+
+#[test]
+fn tax_rate_by_region() {
+    let facts = build_facts();
+    let environment = build_environment();
+
+    let tax = |region: &str| {
+        ConditionValue::Transform(Fn::Product(vec![
+            json(&format!("{}.subtotal", region)),
+            ConditionValue::Transform(Fn::XLookup(
+                Box::new(json(&format!("{}.region", region))),
+                Table::Named("tax_rates".to_string()),
+                Box::new(integer(2)),
+                Some(Box::new(integer(0))),
+                None,
+            )),
+        ]))
+    };
+
+    let cases = vec![
+        (tax("us_order"), float(12.5)),
+        (tax("eu_order"), float(10.0)),
+        (tax("uk_order"), integer(0)),
+    ];
+
+    for (value, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (value, expected),
+        });
+        assert!(evaluation::evaluate_with_environment(
+            &condition,
+            &facts,
+            &environment
+        ));
+    }
+}
+
+#[test]
+fn lookups() {
+    let facts = build_facts();
+    let environment = build_environment();
+
+    let tiers = || {
+        Table::Rows(vec![
+            vec![number(0), number(0), text_fact("none")],
+            vec![number(100), float_fact(0.05), text_fact("silver")],
+            vec![number(500), float_fact(0.1), text_fact("gold")],
+        ])
+    };
+
+    let cases = vec![
+        (
+            Fn::VLookup(
+                Box::new(json("us_order.region")),
+                Table::Named("tax_rates".to_string()),
+                Box::new(integer(2)),
+                Some(Box::new(boolean(false))),
+            ),
+            float(0.0625),
+        ),
+        (
+            Fn::VLookup(Box::new(integer(250)), tiers(), Box::new(integer(3)), None),
+            text("silver"),
+        ),
+        (
+            Fn::VLookup(Box::new(integer(500)), tiers(), Box::new(integer(2)), None),
+            float(0.1),
+        ),
+        (
+            Fn::XLookup(
+                Box::new(integer(250)),
+                tiers(),
+                Box::new(integer(3)),
+                None,
+                Some(Box::new(integer(1))),
+            ),
+            text("gold"),
+        ),
+        (
+            Fn::XLookup(
+                Box::new(integer(250)),
+                tiers(),
+                Box::new(integer(3)),
+                None,
+                Some(Box::new(integer(-1))),
+            ),
+            text("silver"),
+        ),
+        (
+            Fn::XLookup(
+                Box::new(float(100.0)),
+                tiers(),
+                Box::new(integer(3)),
+                None,
+                None,
+            ),
+            text("silver"),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate_with_environment(
+            &condition,
+            &facts,
+            &environment
+        ));
+    }
+}
+
+#[test]
+fn missing_rows_are_not_a_value() {
+    let facts = build_facts();
+    let environment = build_environment();
+
+    let tax_rate = |region: ConditionValue, column: i64| {
+        Fn::VLookup(
+            Box::new(region),
+            Table::Named("tax_rates".to_string()),
+            Box::new(integer(column)),
+            Some(Box::new(boolean(false))),
+        )
+    };
+
+    let cases = vec![
+        tax_rate(json("uk_order.region"), 2),
+        tax_rate(json("us_order.region"), 0),
+        tax_rate(json("us_order.region"), 3),
+        tax_rate(json("missing"), 2),
+        Fn::VLookup(
+            Box::new(text("US")),
+            Table::Named("unknown".to_string()),
+            Box::new(integer(2)),
+            None,
+        ),
+        Fn::VLookup(
+            Box::new(integer(-1)),
+            Table::Rows(vec![vec![number(0), number(1)]]),
+            Box::new(integer(2)),
+            None,
+        ),
+        Fn::XLookup(
+            Box::new(text("UK")),
+            Table::Named("tax_rates".to_string()),
+            Box::new(integer(2)),
+            None,
+            Some(Box::new(integer(2))),
+        ),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate_with_environment(
+            &condition,
+            &facts,
+            &environment
+        ));
+    }
+}
+
+#[test]
+fn tables_are_serialized_with_the_condition() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::VLookup(
+                Box::new(json("eu_order.region")),
+                Table::Rows(vec![
+                    vec![text_fact("EU"), float_fact(0.2)],
+                    vec![text_fact("US"), float_fact(0.0625)],
+                ]),
+                Box::new(integer(2)),
+                Some(Box::new(boolean(false))),
+            )),
+            float(0.2),
+        ),
+    });
+
+    let serialized = serialize_condition(&condition).unwrap();
+    let deserialized = deserialize_condition(&serialized).unwrap();
+    assert!(evaluation::evaluate(&deserialized, &facts));
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "us_order": { "region": "US", "subtotal": 200 },
+        "eu_order": { "region": "EU", "subtotal": 50 },
+        "uk_order": { "region": "UK", "subtotal": 80 },
+    });
+
+    JsonFacts::new(data)
+}
+
+fn build_environment() -> Environment {
+    let mut environment = Environment::new();
+    environment.register_lookup_table(
+        "tax_rates",
+        vec![
+            vec![text_fact("EU"), float_fact(0.2)],
+            vec![text_fact("US"), float_fact(0.0625)],
+            vec![text_fact("CA"), float_fact(0.05)],
+        ],
+    );
+    environment
+}
+
+fn text_fact(value: &str) -> FactValue {
+    FactValue::String(value.to_string())
+}
+
+fn number(value: i64) -> FactValue {
+    FactValue::Number(NumberFact::Integer(value))
+}
+
+fn float_fact(value: f64) -> FactValue {
+    FactValue::Number(NumberFact::Float(value))
+}
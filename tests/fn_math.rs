@@ -0,0 +1,229 @@
+mod common;
+
+use common::{float, integer, json};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn total_with_tax_greater_than_limit() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Product(vec![json("total"), float(1.13)])),
+            json("limit"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn sum_promotes_integers_to_floats() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Sum(vec![json("total"), json("shipping"), integer(1)])),
+            float(103.5),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn divide_by_zero_is_not_a_value() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Divide(Box::new(json("total")), Box::new(integer(0)))),
+            integer(0),
+        ),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Mod(Box::new(json("total")), Box::new(float(0.0)))),
+            integer(0),
+        ),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn overflow_is_not_a_value() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Power(Box::new(integer(10)), Box::new(integer(19)))),
+            integer(0),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result);
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Ceiling(
+                Box::new(integer(i64::MAX)),
+                Box::new(integer(2)),
+            )),
+            integer(0),
+        ),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn rounding() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::Round(Box::new(float(2.345)), Box::new(integer(1))),
+            float(2.3),
+        ),
+        (
+            Fn::Round(Box::new(integer(1250)), Box::new(integer(-2))),
+            integer(1300),
+        ),
+        (
+            Fn::RoundUp(Box::new(float(-2.31)), Box::new(integer(1))),
+            float(-2.4),
+        ),
+        (
+            Fn::RoundDown(Box::new(float(2.39)), Box::new(integer(1))),
+            float(2.3),
+        ),
+        (
+            Fn::Round(Box::new(float(1e300)), Box::new(integer(15))),
+            float(1e300),
+        ),
+        (
+            Fn::RoundUp(Box::new(float(1.7e308)), Box::new(integer(2))),
+            float(1.7e308),
+        ),
+        (
+            Fn::RoundDown(Box::new(float(2.5)), Box::new(integer(400))),
+            float(2.5),
+        ),
+        (
+            Fn::Round(Box::new(float(1.2345e-20)), Box::new(integer(22))),
+            float(1.23e-20),
+        ),
+        (Fn::Trunc(Box::new(float(-8.9)), None), integer(-8)),
+        (Fn::Int(Box::new(float(-8.1))), integer(-9)),
+        (
+            Fn::Ceiling(Box::new(float(2.5)), Box::new(integer(1))),
+            integer(3),
+        ),
+        (
+            Fn::Ceiling(Box::new(integer(-5)), Box::new(integer(2))),
+            integer(-4),
+        ),
+        (
+            Fn::Floor(Box::new(float(2.5)), Box::new(float(0.5))),
+            float(2.5),
+        ),
+        (
+            Fn::Floor(Box::new(integer(7)), Box::new(integer(3))),
+            integer(6),
+        ),
+        (
+            Fn::Floor(
+                Box::new(integer(9_007_199_254_740_993)),
+                Box::new(integer(2)),
+            ),
+            integer(9_007_199_254_740_992),
+        ),
+        (
+            Fn::Ceiling(
+                Box::new(integer(9_007_199_254_740_993)),
+                Box::new(integer(2)),
+            ),
+            integer(9_007_199_254_740_994),
+        ),
+        (
+            Fn::Floor(Box::new(integer(i64::MAX)), Box::new(integer(1))),
+            integer(i64::MAX),
+        ),
+        (
+            Fn::Floor(Box::new(integer(-7)), Box::new(integer(-3))),
+            integer(-6),
+        ),
+        (
+            Fn::Ceiling(Box::new(integer(-7)), Box::new(integer(-3))),
+            integer(-9),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn mod_quotient_abs_and_sqrt() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::Mod(Box::new(integer(-3)), Box::new(integer(2))),
+            integer(1),
+        ),
+        (
+            Fn::Mod(Box::new(integer(3)), Box::new(integer(-2))),
+            integer(-1),
+        ),
+        (
+            Fn::Quotient(Box::new(integer(-7)), Box::new(integer(2))),
+            integer(-3),
+        ),
+        (Fn::Abs(Box::new(integer(-4))), integer(4)),
+        (Fn::Sqrt(Box::new(integer(16))), integer(4)),
+        (
+            Fn::Subtract(Box::new(json("total")), Box::new(json("shipping"))),
+            float(97.5),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "total": 100,
+        "shipping": 2.5,
+        "limit": 110,
+    });
+
+    JsonFacts::new(data)
+}
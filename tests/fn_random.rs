@@ -0,0 +1,134 @@
+mod common;
+
+use common::{float, integer};
+use engine::{
+    conditions::{
+        functions::Fn,
+        operators::{AggregationOperator, ComparisonOperator},
+        Condition, ConditionGroup, ConditionValue, ReferenceValue, SingleCondition,
+    },
+    evaluation::{self, environment::Environment},
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn seeded_runs_replay_exactly() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::LessThan,
+        values: (ConditionValue::Transform(Fn::Rand), float(0.5)),
+    });
+    let draws = |seed: u64| {
+        let environment = build_environment(seed);
+        (0..64)
+            .map(|_| evaluation::evaluate_with_environment(&condition, &facts, &environment))
+            .collect::<Vec<bool>>()
+    };
+
+    assert_eq!(draws(7), draws(7));
+    assert_ne!(draws(7), draws(8));
+}
+
+#[test]
+fn audit_sample_of_transactions() {
+    let facts = build_facts();
+
+    let sampled_count = |count: i64, seed: u64| {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::CountIf(
+                    ReferenceValue {
+                        address: "transactions".to_string(),
+                    },
+                    Box::new(Condition::Single(SingleCondition {
+                        operator: ComparisonOperator::LessThan,
+                        values: (ConditionValue::Transform(Fn::Rand), float(0.05)),
+                    })),
+                )),
+                integer(count),
+            ),
+        });
+        evaluation::evaluate_with_environment(&condition, &facts, &build_environment(seed))
+    };
+
+    assert!(sampled_count(8, 2024));
+    assert!(!sampled_count(8, 2025));
+}
+
+#[test]
+fn rand_between_stays_in_range() {
+    let facts = build_facts();
+    let environment = build_environment(99);
+
+    let in_range = |bottom: i64, top: i64| {
+        let draw = |operator: ComparisonOperator, bound: i64| {
+            Condition::Single(SingleCondition {
+                operator,
+                values: (
+                    ConditionValue::Transform(Fn::RandBetween(
+                        Box::new(integer(bottom)),
+                        Box::new(integer(top)),
+                    )),
+                    integer(bound),
+                ),
+            })
+        };
+        Condition::Group(ConditionGroup {
+            operator: AggregationOperator::Every,
+            conditions: vec![
+                draw(ComparisonOperator::GreaterThanEqualTo, bottom),
+                draw(ComparisonOperator::LessThanEqualTo, top),
+            ],
+        })
+    };
+
+    for _ in 0..100 {
+        assert!(evaluation::evaluate_with_environment(
+            &in_range(1, 6),
+            &facts,
+            &environment
+        ));
+        assert!(evaluation::evaluate_with_environment(
+            &in_range(-3, -3),
+            &facts,
+            &environment
+        ));
+        assert!(evaluation::evaluate_with_environment(
+            &in_range(i64::MIN + 1, i64::MAX - 1),
+            &facts,
+            &environment
+        ));
+    }
+}
+
+#[test]
+fn rand_between_reversed_bounds_is_not_a_value() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::RandBetween(Box::new(integer(10)), Box::new(integer(1)))),
+            integer(i64::MIN),
+        ),
+    });
+
+    let result = evaluation::evaluate_with_environment(&condition, &facts, &build_environment(1));
+    assert!(!result)
+}
+
+fn build_facts() -> JsonFacts {
+    let transactions: Vec<serde_json::Value> =
+        (0..200).map(|id| serde_json::json!({ "id": id })).collect();
+    let data = serde_json::json!({ "transactions": transactions });
+
+    JsonFacts::new(data)
+}
+
+fn build_environment(seed: u64) -> Environment {
+    let mut environment = Environment::new();
+    environment.seed_random(seed);
+    environment
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{boolean, integer, json, text};
+use engine::{
+    conditions::{
+        functions::{Fn, Pattern},
+        operators::{AggregationOperator, ComparisonOperator},
+        ArrayCondition, Condition, ConditionValue, ReferenceValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+    serialization::{deserialize::deserialize_condition, serialize::serialize_condition},
+};
+
+#[test]
+fn every_sku_is_valid() {
+    let facts = build_facts();
+
+    let condition = |array: &str| {
+        Condition::Array(ArrayCondition {
+            operator: AggregationOperator::Every,
+            array: ReferenceValue {
+                address: array.to_string(),
+            },
+            condition: Box::new(Condition::Single(SingleCondition {
+                operator: ComparisonOperator::Equals,
+                values: (
+                    ConditionValue::Transform(Fn::Matches(
+                        Box::new(json(&format!("{}[].sku", array))),
+                        Pattern::new(r"^[A-Z]{3}-\d{4}$"),
+                    )),
+                    boolean(true),
+                ),
+            })),
+        })
+    };
+
+    assert!(evaluation::evaluate(&condition("order_items"), &facts));
+    assert!(!evaluation::evaluate(&condition("returned_items"), &facts));
+}
+
+#[test]
+fn extract_capture_groups() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::Extract(
+                Box::new(json("customer.email")),
+                Pattern::new(r"@(?P<domain>.+)$"),
+                Some(Box::new(text("domain"))),
+            ),
+            "example.co.uk",
+        ),
+        (
+            Fn::Extract(
+                Box::new(json("customer.email")),
+                Pattern::new(r"^([^@]+)@"),
+                Some(Box::new(integer(1))),
+            ),
+            "jane.doe",
+        ),
+        (
+            Fn::Extract(
+                Box::new(json("customer.phone")),
+                Pattern::new(r"\d{3}-\d{4}"),
+                None,
+            ),
+            "555-0100",
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), text(expected)),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn no_match_or_invalid_pattern_is_not_a_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        Fn::Extract(
+            Box::new(json("customer.email")),
+            Pattern::new(r"^\d+$"),
+            None,
+        ),
+        Fn::Extract(
+            Box::new(json("customer.email")),
+            Pattern::new(r"@(.+)$"),
+            Some(Box::new(text("domain"))),
+        ),
+        Fn::Matches(Box::new(json("customer.email")), Pattern::new(r"([a-z")),
+        Fn::Matches(Box::new(json("customer.missing")), Pattern::new(r".*")),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn patterns_are_compiled_once_and_serialized_as_text() {
+    let facts = build_facts();
+
+    let pattern = Pattern::new(r"^\+\d+ \d{3}-\d{4}$");
+    assert!(std::ptr::eq(
+        pattern.regex().unwrap(),
+        pattern.regex().unwrap()
+    ));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Matches(Box::new(json("customer.phone")), pattern)),
+            boolean(true),
+        ),
+    });
+
+    let serialized = serialize_condition(&condition).unwrap();
+    assert!(serialized.contains(r#""^\\+\\d+ \\d{3}-\\d{4}$""#));
+
+    let deserialized = deserialize_condition(&serialized).unwrap();
+    assert!(evaluation::evaluate(&deserialized, &facts));
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "order_items": [
+            { "sku": "ABC-1234" },
+            { "sku": "XYZ-0001" },
+        ],
+        "returned_items": [
+            { "sku": "ABC-1234" },
+            { "sku": "abc-12345" },
+        ],
+        "customer": {
+            "email": "jane.doe@example.co.uk",
+            "phone": "+1 555-0100",
+        },
+    });
+
+    JsonFacts::new(data)
+}
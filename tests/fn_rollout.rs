@@ -0,0 +1,153 @@
+mod common;
+
+use common::{boolean, float, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+    serialization::serialize::serialize_condition,
+    wasm,
+};
+
+#[test]
+fn buckets_are_stable() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (json("user.id"), text("checkout-v2"), 95),
+        (json("user.id"), text("dark-mode"), 93),
+        (text("user-7"), text("checkout-v2"), 93),
+        (text("user-311"), text("checkout-v2"), 19),
+        (json("user.account_number"), text("checkout-v2"), 79),
+    ];
+
+    for (value, salt, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Bucket(Box::new(value), Box::new(salt))),
+                integer(expected),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn rollout_to_percentage() {
+    let facts = build_facts();
+
+    let in_rollout = |percentage: ConditionValue| {
+        Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Rollout(
+                    Box::new(json("user.id")),
+                    Box::new(text("checkout-v2")),
+                    Box::new(percentage),
+                )),
+                boolean(true),
+            ),
+        })
+    };
+
+    assert!(!evaluation::evaluate(&in_rollout(integer(0)), &facts));
+    assert!(!evaluation::evaluate(&in_rollout(integer(95)), &facts));
+    assert!(evaluation::evaluate(&in_rollout(integer(96)), &facts));
+    assert!(evaluation::evaluate(&in_rollout(float(95.5)), &facts));
+    assert!(evaluation::evaluate(&in_rollout(integer(100)), &facts));
+}
+
+#[test]
+fn rollouts_with_different_salts_are_independent() {
+    let facts = JsonFacts::new(serde_json::json!({}));
+
+    let in_rollout = |user: &str, salt: &str| {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Rollout(
+                    Box::new(text(user)),
+                    Box::new(text(salt)),
+                    Box::new(integer(10)),
+                )),
+                boolean(true),
+            ),
+        });
+        evaluation::evaluate(&condition, &facts)
+    };
+
+    // With 10% rollouts about 1% of users should be in both, give or take three standard
+    // deviations.
+    let users = 20_000;
+    for (first, second) in [("pricing", "exp-2024-01"), ("checkout", "search")] {
+        let in_both = (0..users)
+            .map(|i| format!("user-{}", i))
+            .filter(|user| in_rollout(user, first) && in_rollout(user, second))
+            .count();
+        assert!((160..=240).contains(&in_both), "{} users in both", in_both);
+    }
+}
+
+#[test]
+fn missing_user_is_not_in_rollout() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                Fn::Rollout(
+                    Box::new(json("user.missing")),
+                    Box::new(text("checkout-v2")),
+                    Box::new(integer(100)),
+                ),
+            )))),
+            boolean(true),
+        ),
+    });
+
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn wasm_build_matches_native() {
+    let facts = build_facts();
+
+    for percentage in [95, 96] {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Rollout(
+                    Box::new(json("user.id")),
+                    Box::new(text("checkout-v2")),
+                    Box::new(integer(percentage)),
+                )),
+                boolean(true),
+            ),
+        });
+
+        let serialized_condition = serialize_condition(&condition).unwrap();
+        let serialized_facts = build_data().to_string();
+
+        assert_eq!(
+            evaluation::evaluate(&condition, &facts),
+            wasm::evaluate_condition(&serialized_condition, &serialized_facts)
+        );
+    }
+}
+
+fn build_data() -> serde_json::Value {
+    serde_json::json!({
+        "user": {
+            "id": "user-1042",
+            "account_number": 42,
+        },
+    })
+}
+
+fn build_facts() -> JsonFacts {
+    JsonFacts::new(build_data())
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::{boolean, integer, json, text, transform};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::{json_facts::JsonFacts, FactValue, NumberFact},
+};
+
+#[test]
+fn roles_intersect_required_roles() {
+    let facts = build_facts();
+
+    let condition = |address: &str| {
+        Condition::Single(SingleCondition {
+            operator: ComparisonOperator::GreaterThan,
+            values: (
+                transform(Fn::CountA(vec![transform(Fn::Intersection(
+                    vec![json(address)],
+                    vec![text("admin"), text("billing")],
+                ))])),
+                integer(0),
+            ),
+        })
+    };
+
+    assert!(evaluation::evaluate(&condition("alice.roles[]"), &facts));
+    assert!(!evaluation::evaluate(&condition("bob.roles[]"), &facts));
+}
+
+#[test]
+fn set_operations() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::Union(vec![json("alice.roles[]"), json("bob.roles[]")]),
+            array(&["viewer", "billing", "editor"]),
+        ),
+        (
+            Fn::Intersection(vec![json("alice.roles[]")], vec![json("bob.roles[]")]),
+            array(&["viewer"]),
+        ),
+        (
+            Fn::Difference(vec![json("bob.roles[]")], vec![json("alice.roles[]")]),
+            array(&["editor"]),
+        ),
+        (
+            Fn::Difference(vec![text("a"), text("b"), text("a")], vec![]),
+            array(&["a", "b"]),
+        ),
+        (
+            Fn::Contains(vec![json("bob.roles[]")], Box::new(text("editor"))),
+            boolean(true),
+        ),
+        (
+            Fn::Contains(
+                vec![ConditionValue::Fixed(FactValue::Array(vec![
+                    FactValue::Number(NumberFact::Integer(1)),
+                    FactValue::Number(NumberFact::Integer(2)),
+                ]))],
+                Box::new(ConditionValue::Fixed(FactValue::Number(NumberFact::Float(
+                    2.0,
+                )))),
+            ),
+            boolean(true),
+        ),
+        (
+            Fn::Contains(vec![json("bob.roles[]")], Box::new(text("admin"))),
+            boolean(false),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn contains_missing_value_is_not_a_value() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            transform(Fn::IsBlank(Box::new(transform(Fn::Contains(
+                vec![json("alice.roles[]")],
+                Box::new(json("missing")),
+            ))))),
+            boolean(true),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "alice": { "roles": ["viewer", "billing", "viewer"] },
+        "bob": { "roles": ["viewer", "editor"] },
+    });
+
+    JsonFacts::new(data)
+}
+
+fn array(items: &[&str]) -> ConditionValue {
+    ConditionValue::Fixed(FactValue::Array(
+        items
+            .iter()
+            .map(|item| FactValue::String(item.to_string()))
+            .collect(),
+    ))
+}
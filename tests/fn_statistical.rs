@@ -0,0 +1,563 @@
+mod common;
+
+use common::{boolean, float, integer, json};
+use engine::{
+    conditions::{
+        functions::Fn,
+        operators::{AggregationOperator, ComparisonOperator},
+        ArrayCondition, Condition, ConditionValue, ReferenceValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn median_order_value_above_limit() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Median(vec![json("orders[].total")])),
+            integer(50),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn more_than_three_orders() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Rows(ReferenceValue {
+                address: "orders".to_string(),
+            })),
+            integer(3),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn aggregates_over_array() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (Fn::Average(vec![json("orders[].total")]), float(64.0)),
+        (Fn::Median(vec![json("orders[].total")]), integer(60)),
+        (Fn::Mode(vec![json("orders[].total")]), integer(60)),
+        (Fn::Count(vec![json("orders[].total")]), integer(5)),
+        (Fn::CountA(vec![json("orders[].status")]), integer(5)),
+        (
+            Fn::Min(vec![json("orders[].total"), integer(30)]),
+            integer(20),
+        ),
+        (Fn::Max(vec![json("orders[].total")]), integer(120)),
+        (Fn::Sum(vec![json("orders[].total")]), integer(320)),
+        (Fn::VarP(vec![json("orders[].total")]), float(1024.0)),
+        (Fn::Var(vec![json("orders[].total")]), float(1280.0)),
+        (
+            Fn::Percentile(vec![json("orders[].total")], Box::new(float(0.9))),
+            float(96.0),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn stdev_needs_two_values() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Stdev(vec![integer(4)])),
+            integer(-1),
+        ),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::StdevP(vec![integer(2), integer(4)])),
+            integer(1),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn aggregate_within_array_condition() {
+    let facts = build_facts();
+
+    // Every customer's average line amount is at least 10.
+    let condition = Condition::Array(ArrayCondition {
+        operator: AggregationOperator::Every,
+        array: ReferenceValue {
+            address: "customers".to_string(),
+        },
+        condition: Box::new(Condition::Single(SingleCondition {
+            operator: ComparisonOperator::GreaterThanEqualTo,
+            values: (
+                ConditionValue::Transform(Fn::Average(vec![json("customers[].lines[].amount")])),
+                integer(10),
+            ),
+        })),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+
+    let condition = Condition::Array(ArrayCondition {
+        operator: AggregationOperator::Every,
+        array: ReferenceValue {
+            address: "customers".to_string(),
+        },
+        condition: Box::new(Condition::Single(SingleCondition {
+            operator: ComparisonOperator::GreaterThanEqualTo,
+            values: (
+                ConditionValue::Transform(Fn::Average(vec![json("customers[].lines[].amount")])),
+                integer(20),
+            ),
+        })),
+    });
+    assert!(!evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn mixed_types_are_not_a_value() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Average(vec![json("orders[].status")])),
+            integer(0),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result)
+}
+
+#[test]
+fn reading_is_an_anomaly() {
+    let facts = build_facts();
+
+    let z_score = ConditionValue::Transform(Fn::Standardize(
+        Box::new(json("sensor.reading")),
+        Box::new(ConditionValue::Transform(Fn::Average(vec![json(
+            "sensor.history[]",
+        )]))),
+        Box::new(ConditionValue::Transform(Fn::Stdev(vec![json(
+            "sensor.history[]",
+        )]))),
+    ));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::Abs(Box::new(z_score))),
+            integer(3),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::NormDist(
+                Box::new(json("sensor.reading")),
+                Box::new(integer(20)),
+                Box::new(integer(2)),
+                Box::new(boolean(true)),
+            )),
+            float(0.999),
+        ),
+    });
+    assert!(evaluation::evaluate(&condition, &facts));
+}
+
+#[test]
+fn distributions() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::NormDist(
+                Box::new(integer(42)),
+                Box::new(integer(40)),
+                Box::new(float(1.5)),
+                Box::new(boolean(true)),
+            ),
+            float(0.908789),
+        ),
+        (
+            Fn::NormDist(
+                Box::new(integer(42)),
+                Box::new(integer(40)),
+                Box::new(float(1.5)),
+                Box::new(boolean(false)),
+            ),
+            float(0.10934),
+        ),
+        (
+            Fn::NormInv(
+                Box::new(float(0.908789)),
+                Box::new(integer(40)),
+                Box::new(float(1.5)),
+            ),
+            float(42.000002),
+        ),
+        (
+            Fn::NormInv(
+                Box::new(float(0.001)),
+                Box::new(integer(0)),
+                Box::new(integer(1)),
+            ),
+            float(-3.090232),
+        ),
+        (
+            Fn::NormSDist(Box::new(float(1.333333)), Box::new(boolean(true))),
+            float(0.908789),
+        ),
+        (
+            Fn::NormSDist(Box::new(float(1.333333)), Box::new(boolean(false))),
+            float(0.16401),
+        ),
+        (
+            Fn::BinomDist(
+                Box::new(integer(6)),
+                Box::new(integer(10)),
+                Box::new(float(0.5)),
+                Box::new(boolean(false)),
+            ),
+            float(0.205078),
+        ),
+        (
+            Fn::BinomDist(
+                Box::new(integer(6)),
+                Box::new(integer(10)),
+                Box::new(float(0.5)),
+                Box::new(boolean(true)),
+            ),
+            float(0.828125),
+        ),
+        (
+            Fn::BinomDist(
+                Box::new(integer(2)),
+                Box::new(integer(10)),
+                Box::new(float(0.3)),
+                Box::new(boolean(true)),
+            ),
+            float(0.382783),
+        ),
+        (
+            Fn::Standardize(
+                Box::new(integer(42)),
+                Box::new(integer(40)),
+                Box::new(float(1.5)),
+            ),
+            float(1.333333),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Round(
+                    Box::new(ConditionValue::Transform(function)),
+                    Box::new(integer(6)),
+                )),
+                expected,
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn invalid_distribution_arguments_are_not_a_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        Fn::NormInv(
+            Box::new(integer(1)),
+            Box::new(integer(0)),
+            Box::new(integer(1)),
+        ),
+        Fn::NormDist(
+            Box::new(integer(1)),
+            Box::new(integer(0)),
+            Box::new(integer(0)),
+            Box::new(boolean(true)),
+        ),
+        Fn::BinomDist(
+            Box::new(integer(11)),
+            Box::new(integer(10)),
+            Box::new(float(0.5)),
+            Box::new(boolean(true)),
+        ),
+        Fn::BinomDist(
+            Box::new(integer(1)),
+            Box::new(integer(10)),
+            Box::new(float(1.5)),
+            Box::new(boolean(true)),
+        ),
+        // Too many trials to sum over.
+        Fn::BinomDist(
+            Box::new(integer(500_000_000_000)),
+            Box::new(integer(1_000_000_000_000)),
+            Box::new(float(0.5)),
+            Box::new(boolean(true)),
+        ),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn projected_usage_exceeds_quota() {
+    let facts = build_facts();
+
+    let next_month = ConditionValue::Transform(Fn::Sum(vec![
+        ConditionValue::Transform(Fn::Rows(ReferenceValue {
+            address: "usage".to_string(),
+        })),
+        integer(1),
+    ]));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            ConditionValue::Transform(Fn::ForecastLinear(
+                Box::new(next_month),
+                vec![json("usage[].gigabytes")],
+                vec![json("usage[].month")],
+            )),
+            json("quota_gigabytes"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn regression() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::Slope(vec![json("usage[].gigabytes")], vec![json("usage[].month")]),
+            float(1.942857),
+        ),
+        (
+            Fn::Intercept(vec![json("usage[].gigabytes")], vec![json("usage[].month")]),
+            float(8.2),
+        ),
+        (
+            Fn::Forecast(
+                Box::new(integer(7)),
+                vec![json("usage[].gigabytes")],
+                vec![json("usage[].month")],
+            ),
+            float(21.8),
+        ),
+        (
+            Fn::Slope(
+                vec![integer(3), integer(5), integer(7)],
+                vec![integer(1), integer(2), integer(3)],
+            ),
+            integer(2),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Round(
+                    Box::new(ConditionValue::Transform(function)),
+                    Box::new(integer(6)),
+                )),
+                expected,
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn regression_without_a_line() {
+    let facts = build_facts();
+
+    let cases = vec![
+        // Every x is the same.
+        Fn::Slope(vec![integer(1), integer(2)], vec![integer(4), integer(4)]),
+        // The lists differ in length.
+        Fn::Intercept(
+            vec![json("usage[].gigabytes")],
+            vec![integer(1), integer(2)],
+        ),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn regression_skips_incomplete_points() {
+    let facts = JsonFacts::new(serde_json::json!({
+        "readings": [
+            { "x": 1, "y": 3 },
+            { "x": 2 },
+            { "x": 3, "y": 7 },
+            { "y": 100 },
+            { "x": 4, "y": 9 },
+        ],
+    }));
+
+    let cases = vec![
+        (
+            Fn::Slope(vec![json("readings[].y")], vec![json("readings[].x")]),
+            integer(2),
+        ),
+        (
+            Fn::Intercept(vec![json("readings[].y")], vec![json("readings[].x")]),
+            integer(1),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Round(
+                    Box::new(ConditionValue::Transform(function)),
+                    Box::new(integer(6)),
+                )),
+                expected,
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn top_n_and_rank() {
+    let facts = build_facts();
+
+    let totals = || vec![json("orders[].total")];
+    let cases = vec![
+        (Fn::Large(totals(), Box::new(integer(1))), integer(120)),
+        (Fn::Large(totals(), Box::new(integer(2))), integer(60)),
+        (Fn::Small(totals(), Box::new(integer(1))), integer(20)),
+        (Fn::Small(totals(), Box::new(integer(5))), integer(120)),
+        (Fn::Rank(Box::new(integer(120)), totals(), None), integer(1)),
+        (Fn::Rank(Box::new(integer(60)), totals(), None), integer(2)),
+        (Fn::Rank(Box::new(integer(20)), totals(), None), integer(5)),
+        (
+            Fn::Rank(Box::new(integer(120)), totals(), Some(Box::new(integer(1)))),
+            integer(5),
+        ),
+        (
+            Fn::Rank(Box::new(float(60.0)), totals(), Some(Box::new(integer(1)))),
+            integer(2),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+
+    let cases = vec![
+        Fn::Large(totals(), Box::new(integer(0))),
+        Fn::Large(totals(), Box::new(integer(6))),
+        Fn::Small(vec![json("missing[]")], Box::new(integer(1))),
+        Fn::Rank(Box::new(integer(50)), totals(), None),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "orders": [
+            { "total": 20, "status": "shipped" },
+            { "total": 60, "status": "shipped" },
+            { "total": 120, "status": "pending" },
+            { "total": 60, "status": "shipped" },
+            { "total": 60, "status": "cancelled" },
+        ],
+        "quota_gigabytes": 21,
+        "usage": [
+            { "month": 1, "gigabytes": 10 },
+            { "month": 2, "gigabytes": 12 },
+            { "month": 3, "gigabytes": 15 },
+            { "month": 4, "gigabytes": 15 },
+            { "month": 5, "gigabytes": 18 },
+            { "month": 6, "gigabytes": 20 },
+        ],
+        "sensor": {
+            "reading": 31.5,
+            "history": [19.8, 20.4, 20.1, 19.6, 20.3, 19.9],
+        },
+        "customers": [
+            { "lines": [{ "amount": 10 }, { "amount": 30 }] },
+            { "lines": [{ "amount": 12 }] },
+        ],
+    });
+
+    JsonFacts::new(data)
+}
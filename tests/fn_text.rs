@@ -0,0 +1,226 @@
+mod common;
+
+use common::{boolean, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn, operators::ComparisonOperator, Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::{json_facts::JsonFacts, FactValue},
+};
+
+#[test]
+fn trimmed_lower_case_equals() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Lower(Box::new(ConditionValue::Transform(Fn::Trim(
+                Box::new(json("name")),
+            ))))),
+            text("ada   lovelace"),
+        ),
+    });
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result);
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Lower(Box::new(ConditionValue::Transform(Fn::Trim(
+                Box::new(json("name")),
+            ))))),
+            text("ada lovelace"),
+        ),
+    });
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result);
+}
+
+#[test]
+fn len_counts_characters() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Len(Box::new(json("city")))),
+            integer(8),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn left_right_and_mid() {
+    let facts = build_facts();
+
+    let left_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Left(Box::new(json("sku")), Some(Box::new(integer(3))))),
+            text("ABC"),
+        ),
+    });
+    assert!(evaluation::evaluate(&left_condition, &facts));
+
+    let right_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Right(Box::new(json("sku")), None)),
+            text("9"),
+        ),
+    });
+    assert!(evaluation::evaluate(&right_condition, &facts));
+
+    let mid_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Mid(
+                Box::new(json("sku")),
+                Box::new(integer(5)),
+                Box::new(integer(2)),
+            )),
+            text("12"),
+        ),
+    });
+    assert!(evaluation::evaluate(&mid_condition, &facts));
+}
+
+#[test]
+fn concat_converts_numbers() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Concat(vec![json("city"), text("-"), json("floor")])),
+            text("Montréal-3"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn substitute_nth_instance() {
+    let facts = build_facts();
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Substitute(
+                Box::new(json("sku")),
+                Box::new(text("-")),
+                Box::new(text("")),
+                Some(Box::new(integer(2))),
+            )),
+            text("ABC-129"),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn find_and_search() {
+    let facts = build_facts();
+
+    let find_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Find(Box::new(text("-")), Box::new(json("sku")), None)),
+            integer(4),
+        ),
+    });
+    assert!(evaluation::evaluate(&find_condition, &facts));
+
+    let case_sensitive_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Find(Box::new(text("abc")), Box::new(json("sku")), None)),
+            integer(1),
+        ),
+    });
+    assert!(!evaluation::evaluate(&case_sensitive_condition, &facts));
+
+    let search_condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            ConditionValue::Transform(Fn::Search(
+                Box::new(text("abc")),
+                Box::new(json("sku")),
+                None,
+            )),
+            integer(1),
+        ),
+    });
+    assert!(evaluation::evaluate(&search_condition, &facts));
+}
+
+#[test]
+fn split_tags_and_join() {
+    let facts = build_facts();
+
+    let tags = || ConditionValue::Transform(Fn::Split(Box::new(json("tags")), Box::new(text(","))));
+
+    let cases = vec![
+        (
+            tags(),
+            ConditionValue::Fixed(FactValue::Array(vec![
+                FactValue::String("sale".to_string()),
+                FactValue::String("".to_string()),
+                FactValue::String("new".to_string()),
+            ])),
+        ),
+        (
+            ConditionValue::Transform(Fn::CountA(vec![tags()])),
+            integer(3),
+        ),
+        (
+            ConditionValue::Transform(Fn::TextJoin(
+                Box::new(text(" | ")),
+                Box::new(boolean(true)),
+                vec![tags(), json("missing"), json("floor")],
+            )),
+            text("sale | new | 3"),
+        ),
+        (
+            ConditionValue::Transform(Fn::TextJoin(
+                Box::new(text("-")),
+                Box::new(boolean(false)),
+                vec![tags()],
+            )),
+            text("sale--new"),
+        ),
+        (
+            ConditionValue::Transform(Fn::Split(Box::new(text(" ")), Box::new(text(",")))),
+            ConditionValue::Fixed(FactValue::Array(vec![])),
+        ),
+    ];
+
+    for (value, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (value, expected),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "name": "  Ada   LOVELACE ",
+        "city": "Montréal",
+        "floor": 3,
+        "sku": "ABC-12-9",
+        "tags": "sale, ,new",
+    });
+
+    JsonFacts::new(data)
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::{boolean, float, integer, json, text, transform};
+use engine::{
+    conditions::{functions::Fn, operators::ComparisonOperator, Condition, SingleCondition},
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn haversine_distance_within_range() {
+    let facts = build_facts();
+
+    let radians = |address: &str| transform(Fn::Radians(Box::new(json(address))));
+    let half_angle_sine_squared = |a: &str, b: &str| {
+        transform(Fn::Power(
+            Box::new(transform(Fn::Sin(Box::new(transform(Fn::Divide(
+                Box::new(transform(Fn::Subtract(
+                    Box::new(radians(a)),
+                    Box::new(radians(b)),
+                ))),
+                Box::new(integer(2)),
+            )))))),
+            Box::new(integer(2)),
+        ))
+    };
+
+    let haversine = transform(Fn::Sum(vec![
+        half_angle_sine_squared("destination.lat", "origin.lat"),
+        transform(Fn::Product(vec![
+            transform(Fn::Cos(Box::new(radians("origin.lat")))),
+            transform(Fn::Cos(Box::new(radians("destination.lat")))),
+            half_angle_sine_squared("destination.lon", "origin.lon"),
+        ])),
+    ]));
+    let distance_km = transform(Fn::Product(vec![
+        integer(2),
+        integer(6371),
+        transform(Fn::ASin(Box::new(transform(Fn::Sqrt(Box::new(haversine)))))),
+    ]));
+
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::Equals,
+        values: (
+            transform(Fn::Round(Box::new(distance_km), Box::new(integer(0)))),
+            integer(344),
+        ),
+    });
+
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(result)
+}
+
+#[test]
+fn trigonometric_and_logarithmic_values() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (Fn::Degrees(Box::new(transform(Fn::Pi))), integer(180)),
+        (
+            Fn::Degrees(Box::new(transform(Fn::ATan2(
+                Box::new(integer(1)),
+                Box::new(integer(1)),
+            )))),
+            integer(45),
+        ),
+        (
+            Fn::Degrees(Box::new(transform(Fn::ACos(Box::new(float(0.5)))))),
+            integer(60),
+        ),
+        (
+            Fn::Sin(Box::new(transform(Fn::Radians(Box::new(integer(30)))))),
+            float(0.5),
+        ),
+        (
+            Fn::Sec(Box::new(transform(Fn::Radians(Box::new(integer(60)))))),
+            integer(2),
+        ),
+        (Fn::CosH(Box::new(integer(0))), integer(1)),
+        (Fn::TanH(Box::new(integer(0))), integer(0)),
+        (
+            Fn::Log(Box::new(integer(8)), Some(Box::new(integer(2)))),
+            integer(3),
+        ),
+        (Fn::Log(Box::new(integer(1000)), None), integer(3)),
+        (Fn::Log10(Box::new(json("sensor.pressure_pa"))), integer(5)),
+        (
+            Fn::Ln(Box::new(transform(Fn::Exp(Box::new(integer(2)))))),
+            integer(2),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                transform(Fn::Round(
+                    Box::new(transform(function)),
+                    Box::new(integer(9)),
+                )),
+                expected,
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn domain_errors_are_not_a_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        Fn::Ln(Box::new(integer(-1))),
+        Fn::Ln(Box::new(integer(0))),
+        Fn::Log(Box::new(integer(8)), Some(Box::new(integer(1)))),
+        Fn::Log(Box::new(integer(8)), Some(Box::new(integer(-2)))),
+        Fn::Log10(Box::new(json("sensor.offset"))),
+        Fn::ASin(Box::new(integer(2))),
+        Fn::ACos(Box::new(float(-1.5))),
+        Fn::Cot(Box::new(integer(0))),
+        Fn::Csc(Box::new(integer(0))),
+        Fn::ATan2(Box::new(integer(0)), Box::new(integer(0))),
+        Fn::Exp(Box::new(integer(1000))),
+        Fn::Sin(Box::new(text("north"))),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                transform(Fn::IsBlank(Box::new(transform(function)))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "origin": { "lat": 48.8566, "lon": 2.3522 },
+        "destination": { "lat": 51.5074, "lon": -0.1278 },
+        "sensor": { "pressure_pa": 100000, "offset": -3.5 },
+    });
+
+    JsonFacts::new(data)
+}
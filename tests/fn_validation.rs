@@ -0,0 +1,127 @@
+mod common;
+
+use common::{boolean, integer, json, text};
+use engine::{
+    conditions::{
+        functions::Fn,
+        operators::{AggregationOperator, ComparisonOperator},
+        Condition, ConditionGroup, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn onboarding_validation() {
+    let is_valid = |function: fn(Box<ConditionValue>) -> Fn, address: &str| {
+        Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(function(Box::new(json(address)))),
+                boolean(true),
+            ),
+        })
+    };
+    let condition = Condition::Group(ConditionGroup {
+        operator: AggregationOperator::Every,
+        conditions: vec![
+            is_valid(Fn::IsEmail, "applicant.email"),
+            is_valid(Fn::IsPhoneNumber, "applicant.phone"),
+            is_valid(Fn::IsIban, "applicant.iban"),
+            is_valid(Fn::IsLuhn, "applicant.card"),
+            is_valid(Fn::IsUuid, "applicant.id"),
+        ],
+    });
+
+    let applicant = |email: &str| {
+        JsonFacts::new(serde_json::json!({
+            "applicant": {
+                "email": email,
+                "phone": "+447700900123",
+                "iban": "GB82 WEST 1234 5698 7654 32",
+                "card": "4111 1111 1111 1111",
+                "id": "123e4567-e89b-12d3-a456-426614174000",
+            },
+        }))
+    };
+
+    assert!(evaluation::evaluate(
+        &condition,
+        &applicant("jane.doe@example.com")
+    ));
+    assert!(!evaluation::evaluate(
+        &condition,
+        &applicant("jane.doe@example")
+    ));
+}
+
+#[test]
+fn validators() {
+    let facts = JsonFacts::new(serde_json::json!({}));
+
+    let cases = vec![
+        (
+            Fn::IsEmail(Box::new(text("jane.doe+news@example.co.uk"))),
+            true,
+        ),
+        (
+            Fn::IsEmail(Box::new(text("o'brien@mail-1.example.org"))),
+            true,
+        ),
+        (Fn::IsEmail(Box::new(text("jane..doe@example.com"))), false),
+        (Fn::IsEmail(Box::new(text(".jane@example.com"))), false),
+        (Fn::IsEmail(Box::new(text("@example.com"))), false),
+        (Fn::IsEmail(Box::new(text("jane@-example.com"))), false),
+        (Fn::IsEmail(Box::new(text("jane@example..com"))), false),
+        (Fn::IsEmail(Box::new(text("jane@192.168.0.1"))), false),
+        (Fn::IsEmail(Box::new(text("jane doe@example.com"))), false),
+        (Fn::IsPhoneNumber(Box::new(text("+14155550100"))), true),
+        (Fn::IsPhoneNumber(Box::new(text("+1 415 555 0100"))), false),
+        (Fn::IsPhoneNumber(Box::new(text("14155550100"))), false),
+        (Fn::IsPhoneNumber(Box::new(text("+0123456"))), false),
+        (
+            Fn::IsPhoneNumber(Box::new(text("+1234567890123456"))),
+            false,
+        ),
+        (Fn::IsIban(Box::new(text("DE89370400440532013000"))), true),
+        (
+            Fn::IsIban(Box::new(text("gb82 west 1234 5698 7654 32"))),
+            true,
+        ),
+        (
+            Fn::IsIban(Box::new(text("GB82 WEST 1234 5698 7654 33"))),
+            false,
+        ),
+        (
+            Fn::IsIban(Box::new(text("GB82-WEST-1234-5698-7654-32"))),
+            false,
+        ),
+        (Fn::IsIban(Box::new(text("GB82"))), false),
+        (Fn::IsLuhn(Box::new(text("79927398713"))), true),
+        (Fn::IsLuhn(Box::new(text("4111-1111-1111-1111"))), true),
+        (Fn::IsLuhn(Box::new(text("4111 1111 1111 1112"))), false),
+        (Fn::IsLuhn(Box::new(text("0"))), false),
+        (Fn::IsLuhn(Box::new(integer(79927398713))), false),
+        (
+            Fn::IsUuid(Box::new(text("123E4567-E89B-12D3-A456-426614174000"))),
+            true,
+        ),
+        (
+            Fn::IsUuid(Box::new(text("123e4567e89b12d3a456426614174000"))),
+            false,
+        ),
+        (
+            Fn::IsUuid(Box::new(text("123e4567-e89b-12d3-a456-42661417400g"))),
+            false,
+        ),
+        (Fn::IsUuid(Box::new(json("missing"))), false),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), boolean(expected)),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::{boolean, json, text};
+use engine::{
+    conditions::{
+        functions::{Fn, Pattern},
+        operators::ComparisonOperator,
+        Condition, ConditionValue, SingleCondition,
+    },
+    evaluation,
+    facts::json_facts::JsonFacts,
+};
+
+#[test]
+fn referrer_host_ends_with_our_domain() {
+    let facts = build_facts();
+
+    let from_our_domain = |referrer: &str| {
+        Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::Matches(
+                    Box::new(ConditionValue::Transform(Fn::UrlHost(Box::new(json(
+                        referrer,
+                    ))))),
+                    Pattern::new(r"(^|\.)example\.com$"),
+                )),
+                boolean(true),
+            ),
+        })
+    };
+
+    assert!(evaluation::evaluate(
+        &from_our_domain("visit.referrer"),
+        &facts
+    ));
+    assert!(!evaluation::evaluate(
+        &from_our_domain("visit.lookalike"),
+        &facts
+    ));
+    assert!(!evaluation::evaluate(
+        &from_our_domain("visit.bare"),
+        &facts
+    ));
+}
+
+#[test]
+fn url_parts() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (Fn::UrlScheme(Box::new(json("visit.referrer"))), "https"),
+        (
+            Fn::UrlHost(Box::new(json("visit.referrer"))),
+            "shop.example.com",
+        ),
+        (
+            Fn::UrlPath(Box::new(json("visit.referrer"))),
+            "/deals/summer%20sale",
+        ),
+        (
+            Fn::UrlQueryParameter(
+                Box::new(json("visit.referrer")),
+                Box::new(text("utm_source")),
+            ),
+            "news letter",
+        ),
+        (
+            Fn::UrlQueryParameter(Box::new(json("visit.referrer")), Box::new(text("ref"))),
+            "a&b",
+        ),
+        (Fn::UrlPath(Box::new(text("https://example.com"))), "/"),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), text(expected)),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn encode_and_decode() {
+    let facts = build_facts();
+
+    let cases = vec![
+        (
+            Fn::EncodeUrl(Box::new(text("summer sale/50% off"))),
+            "summer%20sale%2F50%25%20off",
+        ),
+        (
+            Fn::EncodeUrl(Box::new(text("café-1_2.3~"))),
+            "caf%C3%A9-1_2.3~",
+        ),
+        (
+            Fn::DecodeUrl(Box::new(text("caf%C3%A9%20au%20lait"))),
+            "café au lait",
+        ),
+        (
+            Fn::DecodeUrl(Box::new(ConditionValue::Transform(Fn::EncodeUrl(
+                Box::new(json("visit.referrer")),
+            )))),
+            "HTTPS://Shop.Example.com/deals/summer%20sale?utm_source=news+letter&ref=a%26b",
+        ),
+    ];
+
+    for (function, expected) in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (ConditionValue::Transform(function), text(expected)),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+#[test]
+fn invalid_urls_are_not_a_value() {
+    let facts = build_facts();
+
+    let cases = vec![
+        Fn::UrlHost(Box::new(json("visit.bare"))),
+        Fn::UrlScheme(Box::new(text("not a url"))),
+        Fn::UrlHost(Box::new(text("mailto:jane@example.com"))),
+        Fn::UrlQueryParameter(Box::new(json("visit.referrer")), Box::new(text("missing"))),
+        Fn::DecodeUrl(Box::new(text("100%"))),
+        Fn::DecodeUrl(Box::new(text("%zz"))),
+        Fn::DecodeUrl(Box::new(text("%C3"))),
+        Fn::DecodeUrl(Box::new(text("%+F"))),
+        Fn::DecodeUrl(Box::new(text("%-1"))),
+    ];
+
+    for function in cases {
+        let condition = Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Transform(Fn::IsBlank(Box::new(ConditionValue::Transform(
+                    function,
+                )))),
+                boolean(true),
+            ),
+        });
+        assert!(evaluation::evaluate(&condition, &facts));
+    }
+}
+
+fn build_facts() -> JsonFacts {
+    let data = serde_json::json!({
+        "visit": {
+            "referrer": "HTTPS://Shop.Example.com/deals/summer%20sale?utm_source=news+letter&ref=a%26b",
+            "lookalike": "https://notexample.com/login",
+            "bare": "example.com/login",
+        },
+    });
+
+    JsonFacts::new(data)
+}
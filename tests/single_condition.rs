@@ -162,10 +162,75 @@ fn float_and_integer_equivalence() {
     assert!(result)
 }
 
+#[test]
+fn single_condition_in_and_not_in_list() {
+    let facts = build_facts();
+
+    let countries = || {
+        ConditionValue::Fixed(FactValue::Array(vec![
+            FactValue::String("CA".to_string()),
+            FactValue::String("US".to_string()),
+            FactValue::String("MX".to_string()),
+        ]))
+    };
+    let condition = |operator: ComparisonOperator, address: &str| {
+        Condition::Single(SingleCondition {
+            operator,
+            values: (
+                ConditionValue::Json(ReferenceValue {
+                    address: address.to_string(),
+                }),
+                countries(),
+            ),
+        })
+    };
+
+    assert!(evaluation::evaluate(
+        &condition(ComparisonOperator::In, "country"),
+        &facts
+    ));
+    assert!(!evaluation::evaluate(
+        &condition(ComparisonOperator::In, "missing"),
+        &facts
+    ));
+    assert!(!evaluation::evaluate(
+        &condition(ComparisonOperator::NotIn, "country"),
+        &facts
+    ));
+    assert!(!evaluation::evaluate(
+        &condition(ComparisonOperator::NotIn, "missing"),
+        &facts
+    ));
+
+    let not_in_list = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::NotIn,
+        values: (
+            ConditionValue::Fixed(FactValue::String("FR".to_string())),
+            countries(),
+        ),
+    });
+    assert!(evaluation::evaluate(&not_in_list, &facts));
+
+    let single_condition = SingleCondition {
+        operator: ComparisonOperator::In,
+        values: (
+            ConditionValue::Json(ReferenceValue {
+                address: "country".to_string(),
+            }),
+            ConditionValue::Fixed(FactValue::String("US".to_string())),
+        ),
+    };
+
+    let condition = Condition::Single(single_condition);
+    let result = evaluation::evaluate(&condition, &facts);
+    assert!(!result)
+}
+
 fn build_facts() -> JsonFacts {
     let data = serde_json::json!({
         "one".to_string(): 1,
         "two_point_one".to_string(): 2.1,
+        "country".to_string(): "US",
     });
 
     JsonFacts::new(data)
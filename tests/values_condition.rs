@@ -0,0 +1,126 @@
+use engine::{
+    conditions::{
+        functions::Fn,
+        operators::{AggregationOperator, ComparisonOperator},
+        ArrayCondition, Condition, ConditionValue, ReferenceValue, SingleCondition,
+        ValuesCondition,
+    },
+    evaluation,
+    facts::{json_facts::JsonFacts, FactValue},
+    serialization::{deserialize::deserialize_condition, serialize::serialize_condition},
+};
+
+#[test]
+fn values_condition_over_split_text() {
+    let facts = JsonFacts::new(serde_json::json!({
+        "tags": "new, sale ,featured",
+        "promoted_tag": "sale",
+    }));
+
+    let any_tag_is = |tag: ConditionValue| {
+        Condition::Values(ValuesCondition {
+            condition: Box::new(Condition::Single(SingleCondition {
+                operator: ComparisonOperator::Equals,
+                values: (
+                    ConditionValue::Json(ReferenceValue {
+                        address: "tag[]".to_string(),
+                    }),
+                    tag,
+                ),
+            })),
+            operator: AggregationOperator::Any,
+            values: ConditionValue::Transform(Fn::Split(
+                Box::new(ConditionValue::Json(ReferenceValue {
+                    address: "tags".to_string(),
+                })),
+                Box::new(ConditionValue::Fixed(FactValue::String(",".to_string()))),
+            )),
+            item: "tag".to_string(),
+        })
+    };
+
+    let promoted = ConditionValue::Json(ReferenceValue {
+        address: "promoted_tag".to_string(),
+    });
+    assert!(evaluation::evaluate(&any_tag_is(promoted), &facts));
+
+    let vip = ConditionValue::Fixed(FactValue::String("vip".to_string()));
+    assert!(!evaluation::evaluate(&any_tag_is(vip), &facts));
+}
+
+#[test]
+fn values_condition_without_values() {
+    let facts = JsonFacts::new(serde_json::json!({}));
+
+    let values_condition = |operator: AggregationOperator| {
+        Condition::Values(ValuesCondition {
+            condition: Box::new(Condition::Single(SingleCondition {
+                operator: ComparisonOperator::Equals,
+                values: (
+                    ConditionValue::Json(ReferenceValue {
+                        address: "tag[]".to_string(),
+                    }),
+                    ConditionValue::Fixed(FactValue::String("sale".to_string())),
+                ),
+            })),
+            operator,
+            values: ConditionValue::Json(ReferenceValue {
+                address: "tags".to_string(),
+            }),
+            item: "tag".to_string(),
+        })
+    };
+
+    assert!(!evaluation::evaluate(
+        &values_condition(AggregationOperator::Any),
+        &facts
+    ));
+    assert!(evaluation::evaluate(
+        &values_condition(AggregationOperator::Every),
+        &facts
+    ));
+}
+
+#[test]
+fn values_condition_within_array_condition() {
+    let facts = JsonFacts::new(serde_json::json!({
+        "customers": [
+            { "tags": "vip" },
+            { "tags": "sale,returning" },
+        ],
+    }));
+
+    let values_condition = Condition::Values(ValuesCondition {
+        condition: Box::new(Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (
+                ConditionValue::Json(ReferenceValue {
+                    address: "tag[]".to_string(),
+                }),
+                ConditionValue::Fixed(FactValue::String("sale".to_string())),
+            ),
+        })),
+        operator: AggregationOperator::Any,
+        values: ConditionValue::Transform(Fn::Split(
+            Box::new(ConditionValue::Json(ReferenceValue {
+                address: "customers[].tags".to_string(),
+            })),
+            Box::new(ConditionValue::Fixed(FactValue::String(",".to_string()))),
+        )),
+        item: "tag".to_string(),
+    });
+
+    let array_condition = Condition::Array(ArrayCondition {
+        condition: Box::new(values_condition),
+        operator: AggregationOperator::Any,
+        array: ReferenceValue {
+            address: "customers".to_string(),
+        },
+    });
+
+    let serialized = serialize_condition(&array_condition).unwrap();
+    let deserialized = deserialize_condition(&serialized).unwrap();
+
+    let result = evaluation::evaluate(&deserialized, &facts);
+    assert!(result)
+}
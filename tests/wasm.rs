@@ -0,0 +1,102 @@
+mod common;
+
+use common::{date, float, integer, text, transform};
+use engine::{
+    conditions::{
+        functions::{Fn, Holidays, Table},
+        operators::{AggregationOperator, ComparisonOperator},
+        Condition, ConditionGroup, ConditionValue, SingleCondition,
+    },
+    evaluation::{self, environment::Environment},
+    facts::json_facts::JsonFacts,
+    serialization::serialize::serialize_condition,
+    wasm,
+};
+
+#[test]
+fn options_provide_the_environment() {
+    let equals = |value: ConditionValue, expected: ConditionValue| {
+        Condition::Single(SingleCondition {
+            operator: ComparisonOperator::Equals,
+            values: (value, expected),
+        })
+    };
+
+    let condition = Condition::Group(ConditionGroup {
+        operator: AggregationOperator::Every,
+        conditions: vec![
+            equals(transform(Fn::Today), date("2022-10-03")),
+            equals(
+                transform(Fn::NetWorkDays(
+                    Box::new(transform(Fn::Today)),
+                    Box::new(date("2022-10-11")),
+                    Some(Holidays::Calendar("canada".to_string())),
+                )),
+                integer(6),
+            ),
+            equals(
+                transform(Fn::VLookup(
+                    Box::new(text("EU")),
+                    Table::Named("tax_rates".to_string()),
+                    Box::new(integer(2)),
+                    None,
+                )),
+                float(0.2),
+            ),
+        ],
+    });
+    let condition = serialize_condition(&condition).unwrap();
+
+    let options = serde_json::json!({
+        "now": "2022-10-03T09:00:00+02:00",
+        "holiday_calendars": { "canada": ["2022-10-10"] },
+        "lookup_tables": {
+            "tax_rates": [
+                [{ "String": "EU" }, { "Number": { "Float": 0.2 } }],
+                [{ "String": "US" }, { "Number": { "Float": 0.0625 } }],
+            ],
+        },
+    })
+    .to_string();
+
+    assert!(wasm::evaluate_condition_with_options(
+        &condition, "{}", &options
+    ));
+    assert!(!wasm::evaluate_condition(&condition, "{}"));
+}
+
+#[test]
+fn seeded_random_matches_native() {
+    let condition = Condition::Single(SingleCondition {
+        operator: ComparisonOperator::GreaterThan,
+        values: (
+            transform(Fn::RandBetween(
+                Box::new(integer(1)),
+                Box::new(integer(100)),
+            )),
+            integer(50),
+        ),
+    });
+    let serialized = serialize_condition(&condition).unwrap();
+
+    let results: Vec<bool> = (0..20)
+        .map(|seed| {
+            let mut environment = Environment::new();
+            environment.seed_random(seed);
+            let native = evaluation::evaluate_with_environment(
+                &condition,
+                &JsonFacts::new(serde_json::json!({})),
+                &environment,
+            );
+
+            let options = serde_json::json!({ "seed": seed }).to_string();
+            assert_eq!(
+                native,
+                wasm::evaluate_condition_with_options(&serialized, "{}", &options)
+            );
+            native
+        })
+        .collect();
+
+    assert!(results.contains(&true) && results.contains(&false));
+}